@@ -111,8 +111,8 @@ impl State {
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
-    ///     .apply( Player::Add(100,"APlayer"))
-    ///     .apply( Character::Add(200,"ACharacter"))
+    ///     .apply( Player::Add(100,"APlayer".into()))
+    ///     .apply( Character::Add(200,"ACharacter".into()))
     ///     .unwrap();
     ///
     /// assert!( character::qry::exists(&state, 200) );
@@ -123,6 +123,71 @@ impl State {
         command.apply_to(self)
     }
 
+    /// Apply a command only when a predicate over the current `State` holds.
+    /// Otherwise the state is passed through unchanged.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let has_members = |state: &State| !scenario::qry::members(state, 100).is_empty();
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .unwrap()
+    ///     .apply_when( has_members, SeqPlay::GrantTurn(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), Vec::<PubId>::new());
+    ///
+    /// let state = state
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .unwrap()
+    ///     .apply_when( has_members, SeqPlay::GrantTurn(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200]);
+    /// ```
+    pub fn apply_when<Q: Fn(&State) -> bool, T: Applicable>(self, predicate: Q, command: T) -> CmdResult<State> {
+        match predicate(&self) {
+            true => command.apply_to(self),
+            false => Ok(self),
+        }
+    }
+
+    /// Apply a command only when `condition` is true.
+    /// Otherwise the state is passed through unchanged.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .unwrap();
+    ///
+    /// let in_order = turn_order::qry::contains(&state, 100, 200);
+    /// let state = state
+    ///     .apply_if( !in_order, SeqPlay::GrantTurn(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200]);
+    ///
+    /// let in_order = turn_order::qry::contains(&state, 100, 200);
+    /// let state = state
+    ///     .apply_if( !in_order, SeqPlay::GrantTurn(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200]);
+    /// ```
+    pub fn apply_if<T: Applicable>(self, condition: bool, command: T) -> CmdResult<State> {
+        match condition {
+            true => command.apply_to(self),
+            false => Ok(self),
+        }
+    }
+
     /// Apply changes to state with a set of data for a command
     ///
     /// This method accepts a vector of values <T> and a closure that accepts
@@ -145,7 +210,7 @@ impl State {
     /// let state = State::default()
     ///     .apply_with(
     ///         vec![100,200,300],
-    ///         |pub_id| Character::Add(pub_id, "Character")
+    ///         |pub_id| Character::Add(pub_id, "Character".into())
     ///     ).apply_with(
     ///         vec![400,500,600],
     ///         |pub_id| move |state| character::cmd::add(state, pub_id, "Character")
@@ -173,6 +238,9 @@ impl State {
 /// defined outside of this crate.
 pub trait ApplicableChainable {
     fn apply<T: Applicable>(self, command: T) -> CmdResult<State>;
+    fn apply_if<T: Applicable>(self, condition: bool, command: T) -> CmdResult<State>;
+    fn apply_try<T: Applicable>(self, command: T) -> CmdResult<State>;
+    fn guard<F: Fn(&State) -> CmdResult<()>>(self, guard_fn: F) -> CmdResult<State>;
 }
 
 
@@ -186,6 +254,87 @@ impl ApplicableChainable for CmdResult<State> {
             Err(_) => self,
         }
     }
+
+    /// Used to conditionally chain results from applying commands.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply_if( true, SeqPlay::AddTurn(100, 200) )
+    ///     .apply_if( false, SeqPlay::AddTurn(100, 300) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200]);
+    /// ```
+    fn apply_if<T: Applicable>(self, condition: bool, command: T) -> CmdResult<State> {
+        match self {
+            Ok(state) => state.apply_if(condition, command),
+            Err(_) => self,
+        }
+    }
+
+    /// Apply a best effort command. **Errors from the command are discarded.**
+    /// If the command fails, the state from before the command is kept and
+    /// the chain continues as though the command was never applied. Errors
+    /// from earlier in the chain are still propagated.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply_try( Character::Add(100, "Duplicate".into()) )
+    ///     .apply( Character::Add(200, "BCharacter".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::name(&state, 100), "ACharacter".to_string());
+    /// assert!(character::qry::exists(&state, 200));
+    ///
+    /// // Prior errors are not swallowed
+    /// let state = State::default()
+    ///     .apply( Character::Remove(100) )
+    ///     .apply_try( Character::Add(100, "ACharacter".into()) );
+    ///
+    /// assert!(state.is_err());
+    /// ```
+    fn apply_try<T: Applicable>(self, command: T) -> CmdResult<State> {
+        match self {
+            Ok(state) => match state.clone().apply(command) {
+                Ok(new_state) => Ok(new_state),
+                Err(_) => Ok(state),
+            },
+            Err(_) => self,
+        }
+    }
+
+    /// Assert an invariant mid-chain. The guard function (e.g. one of the
+    /// `grd` functions) is run against state; an error short-circuits the
+    /// chain, otherwise state passes through unchanged.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( Player::Add(200, "APlayer".into()) )
+    ///     .guard( |state| entity_type::grd::must_be(state, 100, EntityType::Character) )
+    ///     .apply( Character::Rename(100, "Renamed".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::name(&state, 100), "Renamed".to_string());
+    ///
+    /// let result = Ok(state)
+    ///     .guard( |state| entity_type::grd::must_be(state, 200, EntityType::Character) )
+    ///     .apply( Entity::Name(200, "Renamed".into()) );
+    ///
+    /// assert_eq!(result, Err("Entity type must be Character, but found Player".to_string()));
+    /// ```
+    fn guard<F: Fn(&State) -> CmdResult<()>>(self, guard_fn: F) -> CmdResult<State> {
+        let state = self?;
+        guard_fn(&state)?;
+        Ok(state)
+    }
 }
 
 
@@ -202,10 +351,10 @@ impl ApplicableWithChainable for CmdResult<State> {
     /// ```
     /// use yourupnext::prelude::*;
     /// let state = State::default()
-    ///    .apply( Character::Add(100, "ACharacter") )
+    ///    .apply( Character::Add(100, "ACharacter".into()) )
     ///    .apply_with(
     ///        vec![ (200,"BCharacter"), (300, "CCharacter") ],
-    ///         |(pub_id, name)| Character::Add(pub_id, name)
+    ///         |(pub_id, name)| Character::Add(pub_id, name.into())
     ///    );
     /// assert!(state.is_ok());
     /// ```
@@ -221,6 +370,49 @@ impl ApplicableWithChainable for CmdResult<State> {
     }
 }
 
+/// Dry run a set of commands against a state without changing it.
+pub trait ApplicableValidatable {
+    fn validate(&self, state: &State) -> Vec<(usize, CmdErr)>;
+}
+
+impl<T: Applicable + Clone> ApplicableValidatable for Vec<T> {
+    /// Applies each command in order to a clone of `state` and reports the
+    /// index and error of every command that would fail. A failed command
+    /// is skipped so that the commands after it are validated against the
+    /// last good state. The caller's state is never changed.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .unwrap();
+    ///
+    /// let commands = vec![
+    ///     Character::Add(200, "BCharacter".into()),
+    ///     Character::Add(100, "Duplicate".into()),
+    ///     Character::Add(300, "CCharacter".into()),
+    /// ];
+    ///
+    /// let failures = commands.validate(&state);
+    ///
+    /// assert_eq!(failures.len(), 1);
+    /// assert_eq!(failures[0].0, 1);
+    /// assert!(!character::qry::exists(&state, 200));
+    /// assert!(commands.validate(&State::default()).is_empty());
+    /// ```
+    fn validate(&self, state: &State) -> Vec<(usize, CmdErr)> {
+        let mut failures = vec![];
+        let mut working_state = state.clone();
+        for (index, command) in self.iter().enumerate() {
+            match command.clone().apply_to(working_state.clone()) {
+                Ok(new_state) => working_state = new_state,
+                Err(error) => failures.push((index, error)),
+            }
+        }
+        failures
+    }
+}
+
 /// # Command Closure
 /// Allow closures to be used as commands as an `Applicable`,
 /// implementing apply_to() and apply_to_defaut() so that they
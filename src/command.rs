@@ -1,25 +1,76 @@
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// The point of the top level Cmd module is so that all commands can be mixed and matched
 /// providing a composable API for enacting state changes.
-#[derive(Debug, Eq, PartialEq)]
+///
+/// `Cmd` can be serialized so that a list of commands can be stored and replayed.
+///
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let commands = Cmd::Set(vec![
+///     Cmd::AddScenario(100),
+///     Cmd::RenameScenario(100, "Ambush".to_string()),
+///     Cmd::AddCharacter(200, "ACharacter".to_string()),
+///     Cmd::AddCharacter(300, "BCharacter".to_string()),
+///     Cmd::CaptureEntity(100, 200),
+///     Cmd::AddTurn(100, 200),
+///     Cmd::SeqPlay(SeqPlay::AddTurn(100, 300)),
+///     Cmd::MoveTurn(100, 300, TurnPosition::Start),
+///     Cmd::EnableSeqPlay(100),
+///     Cmd::NextTurn(100),
+/// ]);
+///
+/// let state = Cmd::from_json(&commands.to_json().unwrap())
+///     .unwrap()
+///     .apply_to_default()
+///     .unwrap();
+///
+/// assert_eq!(scenario::qry::name(&state, 100), "Ambush".to_string());
+/// assert_eq!(scenario::qry::members(&state, 100), vec![200, 300]);
+/// assert_eq!(turn_order::qry::sequence(&state, 100), vec![300, 200]);
+/// assert_eq!(seq_play::qry::active(&state, 100), Some(300));
+///
+/// let state = Cmd::Set(vec![
+///     Cmd::NextTurn(100),
+///     Cmd::NextTurn(100),
+///     Cmd::NextRound(100),
+///     Cmd::Scenario(Scenario::ReleaseEntity(300)),
+/// ]).apply_to(state).unwrap();
+///
+/// assert_eq!(scenario::qry::members(&state, 100), vec![200]);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Cmd {
     Set(Vec<Cmd>),
     Player(Player),
     Character(Character),
+    Scenario(Scenario),
+    SeqPlay(SeqPlay),
 
     // Player Model
-    AddPlayer(PubId, &'static Name),
-    RenamePlayer(PubId, &'static Name),
+    AddPlayer(PubId, String),
+    RenamePlayer(PubId, String),
     RemovePlayer(PubId),
 
     // Character Model
-    AddCharacter(PubId, &'static Name),
+    AddCharacter(PubId, String),
     AssignCharacterPlayer(PubId, PubId),
-    RenameCharacter(PubId, &'static Name),
+    RenameCharacter(PubId, String),
     RemoveCharacter(PubId),
 
+    // Scenario Model
+    AddScenario(PubId),
+    RenameScenario(PubId, String),
+    CaptureEntity(PubId, PubId),
 
+    // Sequenced Play
+    AddTurn(PubId, PubId),
+    MoveTurn(PubId, PubId, TurnPosition),
+    EnableSeqPlay(PubId),
+    NextTurn(PubId),
+    NextRound(PubId),
 }
 
 impl Applicable for Cmd {
@@ -27,26 +78,44 @@ impl Applicable for Cmd {
         match self {
 
             // Command sets
-            Cmd::Set(cmd_set) => cmd_set.apply_to(state),
+            Cmd::Set(cmd_set) => cmd_set
+                .into_iter()
+                .enumerate()
+                .try_fold(state, |state, (index, cmd)| {
+                    cmd.apply_to(state)
+                        .map_err(|err| format!("Command {} in set failed: {}", index, err))
+                }),
 
             // Model commands
             Cmd::Player(cmd) => cmd.apply_to(state),
             Cmd::Character(cmd) => cmd.apply_to(state),
+            Cmd::Scenario(cmd) => cmd.apply_to(state),
+            Cmd::SeqPlay(cmd) => cmd.apply_to(state),
 
             // Player Model
-            Cmd::AddPlayer(pub_id, name) => Player::Add(pub_id, name).apply_to(state),
-            Cmd::RenamePlayer(pub_id, name) => Player::Rename(pub_id, name).apply_to(state),
+            Cmd::AddPlayer(pub_id, name) => player::cmd::add(state, pub_id, name),
+            Cmd::RenamePlayer(pub_id, name) => player::cmd::rename(state, pub_id, name),
             Cmd::RemovePlayer(pub_id) => state
                     .apply(Character::RemovePlayerFromAll(pub_id))
                     .apply( Player::Remove(pub_id) ),
 
             // Character Model
-            Cmd::AddCharacter(pub_id, name) => Character::Add(pub_id, name).apply_to(state),
+            Cmd::AddCharacter(pub_id, name) => character::cmd::add(state, pub_id, name),
             Cmd::AssignCharacterPlayer(c_pub_id, p_pub_id) => Character::AssignPlayer(c_pub_id, p_pub_id).apply_to(state),
-            Cmd::RenameCharacter(pub_id, name) => Character::Rename(pub_id, name).apply_to(state),
+            Cmd::RenameCharacter(pub_id, name) => character::cmd::rename(state, pub_id, name),
             Cmd::RemoveCharacter(pub_id) => Character::Remove(pub_id).apply_to(state),
 
+            // Scenario Model
+            Cmd::AddScenario(pub_id) => Scenario::Add(pub_id).apply_to(state),
+            Cmd::RenameScenario(pub_id, name) => scenario::cmd::rename(state, pub_id, name),
+            Cmd::CaptureEntity(pub_id, entity_pub_id) => Scenario::CaptureEntity(pub_id, entity_pub_id).apply_to(state),
 
+            // Sequenced Play
+            Cmd::AddTurn(pub_id, entity_pub_id) => SeqPlay::AddTurn(pub_id, entity_pub_id).apply_to(state),
+            Cmd::MoveTurn(pub_id, entity_pub_id, position) => turn_order::cmd::move_turn(state, pub_id, entity_pub_id, position),
+            Cmd::EnableSeqPlay(pub_id) => SeqPlay::Enable(pub_id).apply_to(state),
+            Cmd::NextTurn(pub_id) => SeqPlay::NextTurn(pub_id).apply_to(state),
+            Cmd::NextRound(pub_id) => SeqPlay::NextRound(pub_id).apply_to(state),
         }
     }
 
@@ -55,3 +124,252 @@ impl Applicable for Cmd {
     }
 }
 
+impl Cmd {
+    /// Apply a list of commands, in order, to a state
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = Cmd::apply_all(vec![
+    ///     Cmd::AddPlayer(100, "APlayer".to_string()),
+    ///     Cmd::AddCharacter(200, "ACharacter".to_string()),
+    ///     Cmd::AssignCharacterPlayer(200, 100),
+    /// ], State::default()).unwrap();
+    ///
+    /// assert_eq!(character::qry::player(&state, 200), Some(100));
+    ///
+    /// // The first failing command stops the rest
+    /// let result = Cmd::apply_all(vec![
+    ///     Cmd::AddCharacter(300, "BCharacter".to_string()),
+    ///     Cmd::AddCharacter(200, "Duplicate".to_string()),
+    /// ], state.clone());
+    ///
+    /// assert!(result.is_err());
+    ///
+    /// // A failing command in a set reports its index
+    /// let result = Cmd::Set(vec![
+    ///     Cmd::AddCharacter(300, "BCharacter".to_string()),
+    ///     Cmd::AddCharacter(200, "Duplicate".to_string()),
+    ///     Cmd::AddCharacter(400, "CCharacter".to_string()),
+    /// ]).apply_to(state);
+    ///
+    /// assert_eq!(
+    ///     result,
+    ///     Err("Command 1 in set failed: Entity with PUBLIC ID already exists.".to_string())
+    /// );
+    /// ```
+    pub fn apply_all(cmds: Vec<Cmd>, state: State) -> CmdResult<State> {
+        cmds.apply_to(state)
+    }
+
+    /// Serialize a command (or a `Cmd::Set` of commands) to JSON
+    /// See `command::replay` for tests
+    pub fn to_json(&self) -> Result<String, CmdErr> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+
+    /// Deserialize a command (or a `Cmd::Set` of commands) from JSON
+    /// See `command::replay` for tests
+    pub fn from_json(json: &str) -> Result<Cmd, CmdErr> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+}
+
+/// COMMAND > Rebuild state from scratch by applying commands, in order, to a default state
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let commands = Cmd::Set(vec![
+///     Cmd::AddPlayer(100, "APlayer".to_string()),
+///     Cmd::AddCharacter(200, "ACharacter".to_string()),
+///     Cmd::AssignCharacterPlayer(200, 100),
+///     Cmd::RenameCharacter(200, "BCharacter".to_string()),
+/// ]);
+///
+/// let json = commands.to_json().unwrap();
+/// let Cmd::Set(restored) = Cmd::from_json(&json).unwrap() else { panic!() };
+///
+/// let original = commands.apply_to_default().unwrap();
+/// let replayed = command::replay(restored).unwrap();
+///
+/// assert_eq!(replayed, original);
+/// assert_eq!(character::qry::name(&replayed, 200), "BCharacter".to_string());
+/// assert!(Cmd::from_json("not a command").is_err());
+/// ```
+pub fn replay(cmds: Vec<Cmd>) -> CmdResult<State> {
+    cmds.apply_to_default()
+}
+
+/// The verbs understood by `command::parse` with their arguments
+const PARSE_USAGE: [&str; 7] = [
+    "add-player <pub_id> <name>",
+    "rename-player <pub_id> <name>",
+    "remove-player <pub_id>",
+    "add-character <pub_id> <name>",
+    "rename-character <pub_id> <name>",
+    "remove-character <pub_id>",
+    "assign-character-player <character_pub_id> <player_pub_id>",
+];
+
+/// Parse a line of text into a `Cmd`
+///
+/// A line is a verb followed by its arguments, separated by whitespace.
+/// Names containing spaces can be wrapped in double quotes, and a quote
+/// inside a quoted name is written as `\"`.
+///
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// assert_eq!(command::parse(r#"add-player 100 "Bob""#), Ok(Cmd::AddPlayer(100, "Bob".to_string())));
+/// assert_eq!(command::parse(r#"rename-player 100 "Bobby Tables""#), Ok(Cmd::RenamePlayer(100, "Bobby Tables".to_string())));
+/// assert_eq!(command::parse("remove-player 100"), Ok(Cmd::RemovePlayer(100)));
+/// assert_eq!(command::parse(r#"add-character 200 "Gandalf the Grey""#), Ok(Cmd::AddCharacter(200, "Gandalf the Grey".to_string())));
+/// assert_eq!(command::parse("rename-character 200 Mithrandir"), Ok(Cmd::RenameCharacter(200, "Mithrandir".to_string())));
+/// assert_eq!(command::parse("remove-character 200"), Ok(Cmd::RemoveCharacter(200)));
+/// assert_eq!(command::parse("  assign-character-player  200 100 "), Ok(Cmd::AssignCharacterPlayer(200, 100)));
+/// assert_eq!(command::parse(r#"add-character 300 "The \"Grey\"""#), Ok(Cmd::AddCharacter(300, "The \"Grey\"".to_string())));
+///
+/// let state = command::parse(r#"add-character 200 "Gandalf""#)
+///     .unwrap()
+///     .apply_to_default()
+///     .unwrap();
+/// assert_eq!(character::qry::name(&state, 200), "Gandalf".to_string());
+///
+/// // Malformed input explains what went wrong
+/// assert_eq!(
+///     command::parse("add-character Gandalf"),
+///     Err("Expected 2 arguments, found 1. Usage: add-character <pub_id> <name>".to_string())
+/// );
+/// assert_eq!(
+///     command::parse("add-character abc Gandalf"),
+///     Err("Expected a Public Id, found \"abc\". Usage: add-character <pub_id> <name>".to_string())
+/// );
+/// assert_eq!(
+///     command::parse(r#"add-character 200 "Gandalf"#),
+///     Err("Missing a closing quote".to_string())
+/// );
+/// assert!(command::parse("cast-fireball 200").unwrap_err().starts_with("Unknown command \"cast-fireball\""));
+/// assert_eq!(command::parse(""), Err("Expected a command".to_string()));
+/// ```
+pub fn parse(input: &str) -> Result<Cmd, String> {
+    let tokens = tokenize(input)?;
+    let (verb, args) = match tokens.split_first() {
+        Some((verb, args)) => (verb.as_str(), args),
+        None => return Err("Expected a command".to_string()),
+    };
+
+    let usage = match PARSE_USAGE.iter().find(|usage| usage.split(' ').next() == Some(verb)) {
+        Some(usage) => *usage,
+        None => return Err(format!(
+            "Unknown command \"{}\". Expected one of: {}",
+            verb,
+            PARSE_USAGE.join(", ")
+        )),
+    };
+
+    let expected_args = usage.split(' ').count() - 1;
+    if args.len() != expected_args {
+        return Err(format!("Expected {} arguments, found {}. Usage: {}", expected_args, args.len(), usage));
+    }
+
+    let pub_id = |index: usize| -> Result<PubId, String> {
+        args[index]
+            .parse::<PubId>()
+            .map_err(|_| format!("Expected a Public Id, found \"{}\". Usage: {}", args[index], usage))
+    };
+    let name = |index: usize| args[index].clone();
+
+    match verb {
+        "add-player" => Ok(Cmd::AddPlayer(pub_id(0)?, name(1))),
+        "rename-player" => Ok(Cmd::RenamePlayer(pub_id(0)?, name(1))),
+        "remove-player" => Ok(Cmd::RemovePlayer(pub_id(0)?)),
+        "add-character" => Ok(Cmd::AddCharacter(pub_id(0)?, name(1))),
+        "rename-character" => Ok(Cmd::RenameCharacter(pub_id(0)?, name(1))),
+        "remove-character" => Ok(Cmd::RemoveCharacter(pub_id(0)?)),
+        "assign-character-player" => Ok(Cmd::AssignCharacterPlayer(pub_id(0)?, pub_id(1)?)),
+        _ => Err(format!("Unknown command \"{}\"", verb)),
+    }
+}
+
+/// Split a line into whitespace separated tokens, keeping quoted text together
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let mut token = String::new();
+        if c == '"' {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') if chars.peek() == Some(&'"') => {
+                        token.push('"');
+                        chars.next();
+                    }
+                    Some(c) => token.push(c),
+                    None => return Err("Missing a closing quote".to_string()),
+                }
+            }
+        } else {
+            token.push(c);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                token.push(c);
+            }
+        }
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// COMMAND > Apply a command within the context of a scenario found by a path of
+/// scenario names. Any characters added by the command are captured by the scenario.
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let state = State::default()
+///     .apply( Scenario::Add(100) )
+///     .apply( Scenario::Rename(100, "Campaign".into()) )
+///     .apply( Scenario::Add(200) )
+///     .apply( Scenario::Rename(200, "Dungeon".into()) )
+///     .apply( Scenario::Nest(200, 100) )
+///     .apply(|state| command::apply_in_scenario(
+///         state,
+///         &["Campaign", "Dungeon"],
+///         Cmd::AddCharacter(300, "ACharacter".to_string())
+///     ))
+///     .unwrap();
+///
+/// assert!(character::qry::exists(&state, 300));
+/// assert_eq!(scenario::qry::find_entity(&state, 300), Some(200));
+///
+/// let state = state.apply(|state| command::apply_in_scenario(
+///     state,
+///     &["Campaign", "Cave"],
+///     Cmd::AddCharacter(400, "BCharacter".to_string())
+/// ));
+///
+/// assert!(state.is_err());
+/// ```
+pub fn apply_in_scenario(state: State, scenario_path: &[&str], cmd: Cmd) -> CmdResult<State> {
+    let scenario_pub_id = match scenario::qry::find_by_path(&state, scenario_path) {
+        Some(scenario_pub_id) => scenario_pub_id,
+        None => return Err("Unable to find a scenario for the provided path of scenario names".to_string()),
+    };
+
+    let existing_pub_ids = state.registry.pub_ids();
+    let state = cmd.apply_to(state)?;
+
+    let added_characters: Vec<PubId> = state.registry
+        .pub_ids()
+        .into_iter()
+        .filter(|pub_id| !existing_pub_ids.contains(pub_id) && character::qry::exists(&state, *pub_id))
+        .collect();
+
+    state.apply_with(added_characters, |character_pub_id| {
+        Scenario::CaptureEntity(scenario_pub_id, character_pub_id)
+    })
+}
@@ -1,14 +1,19 @@
 use crate::prelude::*;
 use std::collections::HashMap;
+use std::hash::Hash;
 
 pub trait ComponentValue = Clone + Eq + PartialEq;
 
+/// Components are keyed by an entity's `Id` unless another key is needed,
+/// e.g. `TurnKey` for values that belong to an entity within a scenario.
+pub trait ComponentKey = Clone + Eq + Hash + Ord;
+
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Component<CV: ComponentValue> {
-    pub values: HashMap<Id, CV>,
+pub struct Component<CV: ComponentValue, K: ComponentKey = Id> {
+    pub values: HashMap<K, CV>,
 }
 
-impl<CV: ComponentValue> Default for Component<CV> {
+impl<CV: ComponentValue, K: ComponentKey> Default for Component<CV, K> {
     fn default() -> Self {
         Self {
             values: HashMap::default()
@@ -16,39 +21,128 @@ impl<CV: ComponentValue> Default for Component<CV> {
     }
 }
 
-impl<CV: ComponentValue> Component<CV> {
+impl<CV: ComponentValue, K: ComponentKey> Component<CV, K> {
 
     pub fn is_empty(&self) -> bool {
         self.values.is_empty()
     }
 
-    pub fn is_set(&self, id: Id) -> bool {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (K, &CV)> {
+        self.values.iter().map(|(id, value)| (id.clone(), value))
+    }
+
+    /// Get the Ids that have a value set, in ascending order
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut names: Component<String> = Component::default();
+    /// let _ = names.insert(3, "C".to_string());
+    /// let _ = names.insert(1, "A".to_string());
+    /// let _ = names.insert(2, "B".to_string());
+    ///
+    /// assert_eq!(names.len(), 3);
+    /// assert_eq!(names.ids(), vec![1,2,3]);
+    /// assert_eq!(names.iter().count(), 3);
+    /// ```
+    pub fn ids(&self) -> Vec<K> {
+        let mut ids: Vec<K> = self.values.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    pub fn is_set(&self, id: K) -> bool {
         self.values.contains_key(&id)
     }
 
-    pub fn get(&self, id: Id) -> Option<CV> {
+    pub fn get(&self, id: K) -> Option<CV> {
         self.values.get(&id).cloned()
     }
 
-    pub fn insert(&mut self, id: Id, value: CV) -> CmdResult<()> {
-        if self.is_set(id) {
+    /// Get a value, or `default` if it isn't set
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut counts: Component<usize> = Component::default();
+    /// let _ = counts.insert(1, 10);
+    ///
+    /// assert_eq!(counts.get_or(1, 5), 10);
+    /// assert_eq!(counts.get_or(2, 5), 5);
+    /// assert_eq!(counts.get_or_default(1), 10);
+    /// assert_eq!(counts.get_or_default(2), 0);
+    ///
+    /// // Reading a default doesn't set a value
+    /// assert!(!counts.is_set(2));
+    /// ```
+    pub fn get_or(&self, id: K, default: CV) -> CV {
+        self.get(id).unwrap_or(default)
+    }
+
+    /// Get a value, or the value type's default if it isn't set.
+    /// See `get_or` for tests.
+    pub fn get_or_default(&self, id: K) -> CV where CV: Default {
+        self.get(id).unwrap_or_default()
+    }
+
+    /// Get a value, inserting the value produced by `default` if it isn't set.
+    /// The `default` closure only runs when a value is missing.
+    /// ```
+    /// use yourupnext::prelude::*;
+    /// use std::cell::Cell;
+    ///
+    /// let calls = Cell::new(0);
+    /// let mut counts: Component<usize> = Component::default();
+    ///
+    /// let value = counts.get_or_insert_with(1, || { calls.set(calls.get() + 1); 10 });
+    /// assert_eq!(value, 10);
+    /// assert_eq!(calls.get(), 1);
+    ///
+    /// let value = counts.get_or_insert_with(1, || { calls.set(calls.get() + 1); 20 });
+    /// assert_eq!(value, 10);
+    /// assert_eq!(calls.get(), 1);
+    /// ```
+    pub fn get_or_insert_with(&mut self, id: K, default: impl FnOnce() -> CV) -> CV {
+        self.values.entry(id).or_insert_with(default).clone()
+    }
+
+    pub fn insert(&mut self, id: K, value: CV) -> CmdResult<()> {
+        if self.values.contains_key(&id) {
             return Err("Can not insert component value that already exists. Use update.".to_string());
         }
         self.values.insert(id, value);
         Ok(())
     }
 
-    pub fn update(&mut self, id: Id, value: CV) -> CmdResult<()> {
+    pub fn update(&mut self, id: K, value: CV) -> CmdResult<()> {
         self.values.insert(id, value);
         Ok(())
     }
 
-    pub fn delete(&mut self, id: Id) -> CmdResult<()> {
-        if !self.is_set(id) {
+    /// Keep only the values for which the predicate returns true
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut names: Component<String> = Component::default();
+    /// let _ = names.insert(1, "A".to_string());
+    /// let _ = names.insert(2, "B".to_string());
+    /// let _ = names.insert(3, "C".to_string());
+    /// let _ = names.insert(4, "D".to_string());
+    ///
+    /// names.retain(|id, _| id % 2 == 0);
+    ///
+    /// assert_eq!(names.ids(), vec![2,4]);
+    /// ```
+    pub fn retain(&mut self, f: impl Fn(&K, &CV) -> bool) {
+        self.values.retain(|id, value| f(id, value));
+    }
+
+    pub fn delete(&mut self, id: K) -> CmdResult<()> {
+        if self.values.remove(&id).is_none() {
             return Err("Can not delete component that was never set".to_string());
         }
-
-        self.values.remove(&id);
         Ok(())
     }
 }
@@ -4,17 +4,22 @@ use crate::prelude::*;
 
 pub type Description = str;
 
+/// The maximum number of characters a description can hold
+pub const MAX_DESCRIPTION_LENGTH: usize = 2000;
+
 /// ## Description > Command Applicables (Cmd)
 /// A simple wrapper for entity commands so that they can be composed together with other pipelines.
 /// `Cmd` is a facade for `cmd` functions.
 pub enum Cmd {
     Set(PubId, &'static Description),
+    Append(PubId, &'static Description),
 }
 
 impl Applicable for Cmd {
     fn apply_to(self, state: State) -> CmdResult<State> {
         match self {
             Cmd::Set(pub_id,description) => cmd::set(state, pub_id, description),
+            Cmd::Append(pub_id,text) => cmd::append(state, pub_id, text),
         }
     }
     fn apply_to_default(self) -> CmdResult<State> {
@@ -28,18 +33,56 @@ pub mod cmd {
     use super::*;
 
     /// COMMAND > Set the `Description` of an entity
+    /// Line endings are normalized to `\n` before the description is stored.
     /// ```
     /// use yourupnext::prelude::*;
     ///
     /// let state = Entity::Add(100).apply_to_default().unwrap();
     /// let descriptiond_state = description::Cmd::Set( 100, "ADescription").apply_to(state).unwrap();
-    /// assert_eq!(description::qry::get(&descriptiond_state,100), "ADescription")
+    /// assert_eq!(description::qry::get(&descriptiond_state,100), "ADescription");
+    ///
+    /// let state = description::cmd::set(descriptiond_state, 100, "Line 1\r\nLine 2\rLine 3").unwrap();
+    /// assert_eq!(description::qry::get(&state,100), "Line 1\nLine 2\nLine 3");
+    ///
+    /// let too_long = "x".repeat(description::MAX_DESCRIPTION_LENGTH + 1);
+    /// assert!(description::cmd::set(state, 100, &too_long).is_err());
     /// ```
-    pub fn set(mut state: State, entity_pub_id: PubId, new_description: &'static Description) -> CmdResult<State> {
+    pub fn set(mut state: State, entity_pub_id: PubId, new_description: &Description) -> CmdResult<State> {
+        let new_description = new_description.replace("\r\n", "\n").replace('\r', "\n");
+        grd::within_limit(&new_description, MAX_DESCRIPTION_LENGTH)?;
         let id = entity::qry::id( &state, entity_pub_id);
-        state.description.update(id, new_description.to_string())?;
+        state.description.update(id, new_description)?;
         Ok(state)
     }
+
+    /// COMMAND > Append text to the `Description` of an entity on a new line
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = Entity::Add(100).apply_to_default()
+    ///     .apply( description::Cmd::Set(100, "Session 1") )
+    ///     .apply( description::Cmd::Append(100, "Session 2") )
+    ///     .apply( description::Cmd::Append(100, "Session 3") )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(description::qry::get(&state,100), "Session 1\nSession 2\nSession 3");
+    ///
+    /// // Appending to an empty description doesn't add a leading newline
+    /// let state = state
+    ///     .apply( Entity::Add(200) )
+    ///     .apply( description::Cmd::Append(200, "Session 1") )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(description::qry::get(&state,200), "Session 1");
+    /// ```
+    pub fn append(state: State, entity_pub_id: PubId, text: &Description) -> CmdResult<State> {
+        let existing = qry::get(&state, entity_pub_id);
+        let appended = match existing.is_empty() {
+            true => text.to_string(),
+            false => format!("{}\n{}", existing, text),
+        };
+        set(state, entity_pub_id, &appended)
+    }
 }
 
 /// ## Description > Query (qry)
@@ -58,6 +101,29 @@ pub mod qry {
     /// ```
     pub fn get(state: &State, entity_pub_id: PubId) -> String {
         let id = entity::qry::id( state, entity_pub_id);
-        state.description.get(id).unwrap_or_default()
+        state.description.get_or_default(id)
     }
-}
\ No newline at end of file
+}
+
+/// ## Description > Guard (grd)
+pub mod grd {
+    use super::*;
+
+    /// GUARD > Confirm that a description is no longer than `max_length` characters
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// assert!(description::grd::within_limit("Short", 5).is_ok());
+    /// assert!(description::grd::within_limit("Longer", 5).is_err());
+    /// ```
+    pub fn within_limit(description: &Description, max_length: usize) -> CmdResult<()> {
+        let length = description.chars().count();
+        if length > max_length {
+            return Err(format!(
+                "Descriptions can not be longer than {} characters, but found {}",
+                max_length, length
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum EntityType {
     Player,
     Scenario,
@@ -10,10 +10,11 @@ pub enum EntityType {
     Effect,
     Generic,
     Missing,
+    Custom(String),
 }
 
 impl EntityType {
-    fn label(self) -> String {
+    fn label(&self) -> String {
         match self {
             EntityType::Player => "Player".to_string(),
             EntityType::Scenario => "Scenario".to_string(),
@@ -23,6 +24,22 @@ impl EntityType {
             EntityType::Effect => "Effect".to_string(),
             EntityType::Generic => "Generic".to_string(),
             EntityType::Missing => "Missing".to_string(),
+            EntityType::Custom(label) => label.clone(),
+        }
+    }
+
+    /// The name given to a new entity of this type when no name is provided
+    pub fn default_name(&self) -> &'static Name {
+        match self {
+            EntityType::Player => "New Player",
+            EntityType::Scenario => "New Scenario",
+            EntityType::Character => "New Character",
+            EntityType::Item => "New Item",
+            EntityType::Location => "New Location",
+            EntityType::Effect => "New Effect",
+            EntityType::Generic => "New Entity",
+            EntityType::Missing => "Missing Entity",
+            EntityType::Custom(_) => "New Entity",
         }
     }
 }
@@ -32,8 +49,9 @@ impl EntityType {
 pub mod cmd {
     use super::*;
 
-    /// COMMAND > Set the type of an entity. Attempting to set the `Missing` variant
-    /// will cause an Error.
+    /// COMMAND > Set the type of an entity. Only `Generic` entities can be classified,
+    /// use `reclassify` to intentionally change the type of an entity that is already typed.
+    /// Attempting to set the `Missing` variant will cause an Error.
     /// ```
     /// use yourupnext::prelude::*;
     ///
@@ -43,13 +61,49 @@ pub mod cmd {
     /// let state = entity::cmd::add( state, pub_id).unwrap();
     /// let state = entity_type::cmd::classify( state, pub_id, EntityType::Player ).unwrap();
     /// assert_eq!(entity_type::qry::get(&state, pub_id), EntityType::Player);
+    ///
+    /// // Classifying as the current type is allowed
+    /// let state = entity_type::cmd::classify( state, pub_id, EntityType::Player ).unwrap();
+    ///
+    /// // Typed entities can't be classified as something else
+    /// assert!(entity_type::cmd::classify( state.clone(), pub_id, EntityType::Character ).is_err());
+    ///
+    /// // Missing entities can't be classified
+    /// assert!(entity_type::cmd::classify( state, 200, EntityType::Character ).is_err());
     /// ```
-    pub fn classify(mut state: State, pub_id: PubId, entity_type: EntityType) -> CmdResult<State> {
-        let id = entity::qry::id(&state, pub_id);
+    pub fn classify(state: State, pub_id: PubId, entity_type: EntityType) -> CmdResult<State> {
+        let current_entity_type = qry::get(&state, pub_id);
+        if current_entity_type != EntityType::Generic && current_entity_type != entity_type {
+            return Err(format!(
+                "Can not classify a {:?} entity as {:?}. Use reclassify to change its type.",
+                current_entity_type, entity_type
+            ));
+        }
+        reclassify(state, pub_id, entity_type)
+    }
+
+    /// COMMAND > Change the type of an entity, regardless of its current type.
+    /// Attempting to set the `Missing` variant will cause an Error.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .unwrap();
+    ///
+    /// let state = entity_type::cmd::reclassify( state, 100, EntityType::Character ).unwrap();
+    /// assert_eq!(entity_type::qry::get(&state, 100), EntityType::Character);
+    ///
+    /// assert!(entity_type::cmd::reclassify( state.clone(), 100, EntityType::Missing ).is_err());
+    /// assert!(entity_type::cmd::reclassify( state, 200, EntityType::Player ).is_err());
+    /// ```
+    pub fn reclassify(mut state: State, pub_id: PubId, entity_type: EntityType) -> CmdResult<State> {
+        entity::grd::must_exist(&state, pub_id)?;
         if entity_type == EntityType::Missing {
             return Err("Can not manually classify entities as Missing entity type".to_string());
         }
-        state.entity_type.insert(id, entity_type)?;
+        let id = entity::qry::id(&state, pub_id);
+        state.entity_type.update(id, entity_type)?;
         Ok(state)
     }
 }
@@ -108,6 +162,16 @@ pub mod qry {
     /// // An entity can not be classified as missing
     /// assert!(state.is_err());
     ///
+    /// // Game specific kinds can be represented with `Custom`
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(200) )
+    ///     .apply( Entity::Add(300) )
+    ///     .apply( Entity::Classify(300, EntityType::Custom("Vehicle".to_string())) )
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 200, 300) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entity_type::qry::get(&state, 300), EntityType::Custom("Vehicle".to_string()));
+    /// assert_eq!(turn_order::qry::sequence(&state, 200), vec![300]);
     /// ```
     pub fn get(state: &State, pub_id: PubId) -> EntityType {
         let id = entity::qry::id(state, pub_id);
@@ -119,6 +183,32 @@ pub mod qry {
             _ => EntityType::Generic,
         }
     }
+
+    /// QUERY > Get the number of registered entities of a type
+    /// See `entity::qry::count` for more tests
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .unwrap();
+    ///
+    /// // Entries for unregistered Ids, e.g. in a corrupted save file, aren't counted
+    /// state.entity_type.insert(99, EntityType::Character).unwrap();
+    ///
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Generic), 1);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Character), 1);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Missing), 0);
+    /// ```
+    pub fn count_of(state: &State, entity_type: EntityType) -> usize {
+        // Generic entities don't need to be classified, so each registered entity's type is resolved
+        state.registry
+            .pub_ids()
+            .into_iter()
+            .filter(|pub_id| get(state, *pub_id) == entity_type)
+            .count()
+    }
 }
 
 pub mod grd {
@@ -129,7 +219,7 @@ pub mod grd {
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
-    ///     .apply( Cmd::AddCharacter(100,"ACharacter"))
+    ///     .apply( Cmd::AddCharacter(100,"ACharacter".to_string()))
     ///     .unwrap();
     /// assert!(entity_type::grd::must_be(&state,100,EntityType::Character).is_ok());
     /// assert!(entity_type::grd::must_be(&state,100,EntityType::Player).is_err());
@@ -154,7 +244,7 @@ pub mod grd {
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
-    ///     .apply( Cmd::AddCharacter(100,"ACharacter"))
+    ///     .apply( Cmd::AddCharacter(100,"ACharacter".to_string()))
     ///     .unwrap();
     /// assert!(entity_type::grd::must_not_be(&state,100,EntityType::Character).is_err());
     /// assert!(entity_type::grd::must_not_be(&state,100,EntityType::Player).is_ok());
@@ -164,7 +254,7 @@ pub mod grd {
         pub_id: PubId,
         disallowed_entity_type: EntityType,
     ) -> CmdResult<()> {
-        if must_be(state,pub_id, disallowed_entity_type).is_ok() {
+        if must_be(state,pub_id, disallowed_entity_type.clone()).is_ok() {
             return Err(format!(
                 "Entity type must not be {:?}",
                 disallowed_entity_type
@@ -0,0 +1,142 @@
+use crate::prelude::*;
+use crate::component::component::{ComponentKey, ComponentValue};
+use std::collections::{HashMap, VecDeque};
+use std::ops::Deref;
+
+/// A `Component` that remembers its most recent values.
+///
+/// Every `insert` and `update` is recorded in a per-Id ring buffer holding at
+/// most `capacity` values, oldest first. A capacity of 0 (the default) records
+/// nothing, so history is opt-in. Deleting a value also forgets its history.
+/// The read only `Component` methods are available through `Deref`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct History<CV: ComponentValue, K: ComponentKey = Id> {
+    component: Component<CV, K>,
+    capacity: usize,
+    recent: HashMap<K, VecDeque<CV>>,
+}
+
+impl<CV: ComponentValue, K: ComponentKey> Default for History<CV, K> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<CV: ComponentValue, K: ComponentKey> Deref for History<CV, K> {
+    type Target = Component<CV, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.component
+    }
+}
+
+impl<CV: ComponentValue, K: ComponentKey> History<CV, K> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            component: Component::default(),
+            capacity,
+            recent: HashMap::default(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change how many values are kept per Id, dropping the oldest values
+    /// that no longer fit.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        for values in self.recent.values_mut() {
+            while values.len() > capacity {
+                values.pop_front();
+            }
+        }
+        self.recent.retain(|_, values| !values.is_empty());
+    }
+
+    /// Update a value, recording it in the Id's history
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut hp: History<i32> = History::new(3);
+    /// for value in [20, 15, 12, 9] {
+    ///     let _ = hp.update(1, value);
+    /// }
+    ///
+    /// assert_eq!(hp.get(1), Some(9));
+    /// assert_eq!(hp.history(1), vec![15, 12, 9]);
+    /// assert_eq!(hp.history(2), Vec::<i32>::new());
+    ///
+    /// hp.set_capacity(2);
+    /// assert_eq!(hp.history(1), vec![12, 9]);
+    ///
+    /// // Without a capacity nothing is recorded
+    /// let mut untracked: History<i32> = History::default();
+    /// let _ = untracked.update(1, 20);
+    /// assert_eq!(untracked.get(1), Some(20));
+    /// assert_eq!(untracked.history(1), Vec::<i32>::new());
+    /// ```
+    pub fn update(&mut self, id: K, value: CV) -> CmdResult<()> {
+        self.record(id.clone(), &value);
+        self.component.update(id, value)
+    }
+
+    /// Insert a value that isn't set yet, recording it in the Id's history
+    /// See `History::delete` for tests
+    pub fn insert(&mut self, id: K, value: CV) -> CmdResult<()> {
+        self.component.insert(id.clone(), value.clone())?;
+        self.record(id, &value);
+        Ok(())
+    }
+
+    /// Delete a value along with its history
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut hp: History<i32> = History::new(3);
+    /// let _ = hp.insert(1, 20);
+    /// let _ = hp.update(1, 15);
+    ///
+    /// assert!( hp.insert(1, 10).is_err() );
+    /// assert_eq!(hp.history(1), vec![20, 15]);
+    ///
+    /// let _ = hp.delete(1);
+    /// assert_eq!(hp.get(1), None);
+    /// assert_eq!(hp.history(1), Vec::<i32>::new());
+    ///
+    /// // A value set again starts a fresh history
+    /// let _ = hp.insert(1, 12);
+    /// assert_eq!(hp.history(1), vec![12]);
+    /// ```
+    pub fn delete(&mut self, id: K) -> CmdResult<()> {
+        self.recent.remove(&id);
+        self.component.delete(id)
+    }
+
+    fn record(&mut self, id: K, value: &CV) {
+        if self.capacity == 0 {
+            return;
+        }
+        let values = self.recent.entry(id).or_default();
+        if values.len() == self.capacity {
+            values.pop_front();
+        }
+        values.push_back(value.clone());
+    }
+
+    /// The recorded values for an Id, oldest first
+    pub fn history(&self, id: K) -> Vec<CV> {
+        self.recent
+            .get(&id)
+            .map(|values| values.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Keep only the values (and their history) for which the predicate returns true
+    pub fn retain(&mut self, f: impl Fn(&K, &CV) -> bool) {
+        self.component.retain(f);
+        let component = &self.component;
+        self.recent.retain(|id, _| component.is_set(id.clone()));
+    }
+}
@@ -0,0 +1,95 @@
+/// # Initiative Component
+/// The value used to decide where an entity's turn falls within a sequence
+/// of turns. Higher initiatives act first.
+use crate::prelude::*;
+
+pub type Initiative = i32;
+
+/// ## Initiative > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Set the `Initiative` of an entity
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply( |state| initiative::cmd::set(state, 100, 15) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(initiative::qry::get(&state, 100), Some(15));
+    ///
+    /// // The entity must exist
+    /// assert!( state.apply( |state| initiative::cmd::set(state, 200, 15) ).is_err() );
+    /// ```
+    pub fn set(mut state: State, pub_id: PubId, initiative: Initiative) -> CmdResult<State> {
+        if !entity::qry::exists(&state, pub_id) {
+            return Err("Can not set the initiative of a non existent entity".to_string());
+        }
+        let id = entity::qry::id(&state, pub_id);
+        state.initiative.update(id, initiative)?;
+        Ok(state)
+    }
+
+    /// COMMAND > Roll the `Initiative` of an entity from a seed
+    ///
+    /// Rolls `1..=die` and adds `modifier`. The same seed always gives the
+    /// same roll. See `roll_with` to supply your own `Rng`.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( |state| initiative::cmd::roll(state, 100, 20, 2, 1234) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(initiative::qry::get(&state, 100), Some(18));
+    ///
+    /// // A die must have sides
+    /// assert!( state.clone().apply( |state| initiative::cmd::roll(state, 100, 0, 2, 1234) ).is_err() );
+    ///
+    /// // The entity must exist
+    /// assert!( state.apply( |state| initiative::cmd::roll(state, 200, 20, 2, 1234) ).is_err() );
+    /// ```
+    pub fn roll(state: State, pub_id: PubId, die: u8, modifier: i8, seed: u64) -> CmdResult<State> {
+        roll_with(state, pub_id, die, modifier, &mut SeededRng::new(seed))
+    }
+
+    /// COMMAND > Roll the `Initiative` of an entity with a provided `Rng`
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// struct AlwaysHigh;
+    /// impl Rng for AlwaysHigh {
+    ///     fn next_u64(&mut self) -> u64 { 19 }
+    /// }
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( |state| initiative::cmd::roll_with(state, 100, 20, -1, &mut AlwaysHigh) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(initiative::qry::get(&state, 100), Some(19));
+    /// ```
+    pub fn roll_with(state: State, pub_id: PubId, die: u8, modifier: i8, rng: &mut impl Rng) -> CmdResult<State> {
+        if die == 0 {
+            return Err("Can not roll initiative with a die that has no sides".to_string());
+        }
+        let rolled = rng.roll(die) + modifier as Initiative;
+        set(state, pub_id, rolled)
+    }
+}
+
+/// ## Initiative > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Get the `Initiative` of an entity if it has been set
+    /// See `initiative::cmd::set` for tests
+    pub fn get(state: &State, pub_id: PubId) -> Option<Initiative> {
+        let id = entity::qry::id(state, pub_id);
+        state.initiative.get(id)
+    }
+}
@@ -1,7 +1,11 @@
 pub mod component;
+pub mod history;
 pub mod entity_type;
 pub mod name;
 pub mod description;
 pub mod turn_state;
 pub mod turn_count;
-pub mod turn_order;
\ No newline at end of file
+pub mod turn_order;
+pub mod initiative;
+pub mod tags;
+pub mod stat;
\ No newline at end of file
@@ -1,6 +1,7 @@
 /// # Name Component
 
 use crate::prelude::*;
+use std::borrow::Cow;
 
 pub type Name = str;
 
@@ -8,13 +9,15 @@ pub type Name = str;
 /// A simple wrapper for entity commands so that they can be composed together with other pipelines.
 /// `Cmd` is a facade for `cmd` functions.
 pub enum Cmd {
-    Set(PubId, &'static Name),
+    Set(PubId, Cow<'static, Name>),
+    Rename(PubId, Cow<'static, Name>),
 }
 
 impl Applicable for Cmd {
     fn apply_to(self, state: State) -> CmdResult<State> {
         match self {
             Cmd::Set(pub_id,name) => cmd::set(state, pub_id, name),
+            Cmd::Rename(pub_id,name) => cmd::rename(state, pub_id, name),
         }
     }
     fn apply_to_default(self) -> CmdResult<State> {
@@ -28,18 +31,49 @@ pub mod cmd {
     use super::*;
 
     /// COMMAND > Set the `Name` of an entity
+    /// Surrounding whitespace is trimmed before the name is stored.
     /// ```
     /// use yourupnext::prelude::*;
     ///
     /// let state = Entity::Add(100).apply_to_default().unwrap();
-    /// let renamed_state = name::Cmd::Set( 100, "AName").apply_to(state).unwrap();
-    /// assert_eq!(name::qry::get(&renamed_state,100), "AName")
+    /// let renamed_state = name::Cmd::Set( 100, "AName".into()).apply_to(state).unwrap();
+    /// assert_eq!(name::qry::get(&renamed_state,100), "AName");
+    ///
+    /// let padded_state = name::Cmd::Set( 100, "  Bob  ".into()).apply_to(renamed_state).unwrap();
+    /// assert_eq!(name::qry::get(&padded_state,100), "Bob");
+    ///
+    /// assert!(name::Cmd::Set( 100, "".into()).apply_to(padded_state.clone()).is_err());
+    /// assert!(name::Cmd::Set( 100, " \t ".into()).apply_to(padded_state).is_err());
     /// ```
-    pub fn set(mut state: State, entity_pub_id: PubId, new_name: &'static Name) -> CmdResult<State> {
+    pub fn set(mut state: State, entity_pub_id: PubId, new_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
+        let new_name = new_name.into();
+        grd::valid(&new_name)?;
         let id = entity::qry::id( &state, entity_pub_id);
-        state.name.update(id, new_name.to_string())?;
+        state.name.update(id, new_name.trim().to_string())?;
         Ok(state)
     }
+
+    /// COMMAND > Rename an entity, erroring if the new `Name` matches the current one
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = Entity::Add(100).apply_to_default()
+    ///     .apply( name::Cmd::Set(100, "AName".into()) )
+    ///     .unwrap();
+    ///
+    /// let renamed_state = name::Cmd::Rename(100, "BName".into()).apply_to(state).unwrap();
+    /// assert_eq!(name::qry::get(&renamed_state,100), "BName");
+    ///
+    /// assert!(name::Cmd::Rename(100, "BName".into()).apply_to(renamed_state.clone()).is_err());
+    /// assert!(name::Cmd::Rename(100, " BName ".into()).apply_to(renamed_state).is_err());
+    /// ```
+    pub fn rename(state: State, entity_pub_id: PubId, new_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
+        let new_name = new_name.into();
+        if qry::get(&state, entity_pub_id) == new_name.trim() {
+            return Err("Can not rename an entity to the name it already has".to_string());
+        }
+        set(state, entity_pub_id, new_name)
+    }
 }
 
 /// ## Name > Query (qry)
@@ -52,12 +86,33 @@ pub mod qry {
     /// use yourupnext::prelude::*;
     ///
     /// let state = Entity::Add(100).apply_to_default().unwrap();
-    /// let state = name::Cmd::Set(100, "AName").apply_to(state).unwrap();
+    /// let state = name::Cmd::Set(100, "AName".into()).apply_to(state).unwrap();
     ///
     /// assert_eq!(name::qry::get(&state,100), "AName".to_string() );
     /// ```
     pub fn get(state: &State, entity_pub_id: PubId) -> String {
         let id = entity::qry::id( state, entity_pub_id);
-        state.name.get(id).unwrap_or_default()
+        state.name.get_or_default(id)
     }
-}
\ No newline at end of file
+}
+
+/// ## Name > Guard (grd)
+pub mod grd {
+    use super::*;
+
+    /// GUARD > Confirm that a name isn't empty or only whitespace
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// assert!(name::grd::valid("Bob").is_ok());
+    /// assert!(name::grd::valid("  Bob  ").is_ok());
+    /// assert!(name::grd::valid("").is_err());
+    /// assert!(name::grd::valid("   ").is_err());
+    /// ```
+    pub fn valid(name: &Name) -> CmdResult<()> {
+        if name.trim().is_empty() {
+            return Err("Names can not be empty or only whitespace".to_string());
+        }
+        Ok(())
+    }
+}
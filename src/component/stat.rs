@@ -0,0 +1,158 @@
+/// # Stat Component
+/// Named integer stats (HP, AC, etc) for an entity. A stat that has never
+/// been set has a value of 0.
+use crate::prelude::*;
+use std::collections::HashMap;
+
+pub type StatValue = i32;
+
+pub type Stats = HashMap<String, StatValue>;
+
+/// Copies of an entity's stats stashed under named slots
+pub type StatStash = HashMap<String, Stats>;
+
+/// Stats are stored per (entity Id, stat name) so that each stat keeps its own history
+pub type StatKey = (Id, String);
+
+/// ## Stat > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Set the value of an entity's stat
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 20) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::stat(&state, 100, "hp"), 20);
+    ///
+    /// // The entity must exist
+    /// assert!( state.apply( |state| stat::cmd::set_stat(state, 200, "hp", 20) ).is_err() );
+    /// ```
+    pub fn set_stat(mut state: State, pub_id: PubId, stat_name: &str, value: StatValue) -> CmdResult<State> {
+        if !entity::qry::exists(&state, pub_id) {
+            return Err("Can not set a stat for a non existent entity".to_string());
+        }
+
+        let id = entity::qry::id(&state, pub_id);
+        state.stats.update((id, stat_name.to_string()), value)?;
+        Ok(state)
+    }
+
+    /// COMMAND > Adjust the value of an entity's stat by a delta
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 20) )
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 100, "hp", 5) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::stat(&state, 100, "hp"), 25);
+    ///
+    /// let state = state
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 100, "hp", -10) )
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 100, "ac", -2) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::stat(&state, 100, "hp"), 15);
+    /// assert_eq!(stat::qry::stat(&state, 100, "ac"), -2);
+    ///
+    /// // Adjustments that overflow a stat are an error
+    /// let state = state
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "xp", StatValue::MAX) )
+    ///     .unwrap();
+    ///
+    /// assert!( state.apply( |state| stat::cmd::adjust_stat(state, 100, "xp", 1) ).is_err() );
+    /// ```
+    pub fn adjust_stat(state: State, pub_id: PubId, stat_name: &str, delta: StatValue) -> CmdResult<State> {
+        let value = match qry::stat(&state, pub_id, stat_name).checked_add(delta) {
+            Some(value) => value,
+            None => return Err(format!("Can not adjust stat \"{}\", the result is out of range", stat_name)),
+        };
+        set_stat(state, pub_id, stat_name, value)
+    }
+}
+
+/// ## Stat > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Get the value of an entity's stat, or 0 if it hasn't been set
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::stat(&state, 100, "hp"), 0);
+    /// assert_eq!(stat::qry::stat(&state, 200, "hp"), 0);
+    /// ```
+    pub fn stat(state: &State, pub_id: PubId, stat_name: &str) -> StatValue {
+        let id = entity::qry::id(state, pub_id);
+        state.stats.get_or(key(id, stat_name), 0)
+    }
+
+    /// QUERY > Get all of the stats of an entity
+    /// See `stat::cmd::set_stat` for tests
+    pub fn stats(state: &State, pub_id: PubId) -> Stats {
+        let id = entity::qry::id(state, pub_id);
+        state.stats
+            .iter()
+            .filter(|((stat_id, _), _)| *stat_id == id)
+            .map(|((_, stat_name), value)| (stat_name, *value))
+            .collect()
+    }
+
+    /// QUERY > Get the key of an entity's stat
+    pub fn key(id: Id, stat_name: &str) -> StatKey {
+        (id, stat_name.to_string())
+    }
+
+    /// QUERY > Get the recent values of an entity's stat, oldest first
+    ///
+    /// Only recorded when history is enabled with `State::track_history`.
+    /// Each stat keeps its own history, so updating other stats doesn't use
+    /// up this stat's capacity.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .track_history(3)
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 20) )
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 100, "hp", -5) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "ac", 12) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "dex", 14) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::history(&state, 100, "hp"), vec![20, 15]);
+    /// assert_eq!(stat::qry::history(&state, 100, "ac"), vec![12]);
+    ///
+    /// // Repeated values are real updates and are kept, up to the capacity
+    /// let state = state
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 15) )
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 100, "hp", -3) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::history(&state, 100, "hp"), vec![15, 15, 12]);
+    ///
+    /// // Nothing is recorded unless history is tracked
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 20) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::history(&state, 100, "hp"), Vec::<StatValue>::new());
+    /// ```
+    pub fn history(state: &State, pub_id: PubId, stat_name: &str) -> Vec<StatValue> {
+        let id = entity::qry::id(state, pub_id);
+        state.stats.history(key(id, stat_name))
+    }
+}
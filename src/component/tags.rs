@@ -0,0 +1,130 @@
+/// # Tags Component
+/// Arbitrary labels ("boss", "minion", "npc") applied to entities so that they
+/// can be filtered. Each tag is stored once per entity.
+use crate::prelude::*;
+
+pub type Tag = str;
+
+/// ## Tags > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Add a `Tag` to an entity. Adding a tag that is already set has no effect.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "boss") )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "npc") )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "boss") )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(tags::qry::tags(&state, 100), vec!["boss".to_string(), "npc".to_string()]);
+    ///
+    /// // The entity must exist
+    /// assert!( state.apply( |state| tags::cmd::add_tag(state, 200, "boss") ).is_err() );
+    /// ```
+    pub fn add_tag(mut state: State, pub_id: PubId, tag: &Tag) -> CmdResult<State> {
+        if !entity::qry::exists(&state, pub_id) {
+            return Err("Can not tag a non existent entity".to_string());
+        }
+
+        let id = entity::qry::id(&state, pub_id);
+        let mut tags = state.tags.get_or_default(id);
+
+        if tags.iter().any(|existing| existing == tag) {
+            return Ok(state);
+        }
+
+        tags.push(tag.to_string());
+        state.tags.update(id, tags)?;
+        Ok(state)
+    }
+
+    /// COMMAND > Remove a `Tag` from an entity
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "boss") )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "npc") )
+    ///     .apply( |state| tags::cmd::remove_tag(state, 100, "boss") )
+    ///     .unwrap();
+    ///
+    /// assert!( !tags::qry::has_tag(&state, 100, "boss") );
+    /// assert!( tags::qry::has_tag(&state, 100, "npc") );
+    ///
+    /// // A tag that isn't set can't be removed
+    /// assert!( state.clone().apply( |state| tags::cmd::remove_tag(state, 100, "boss") ).is_err() );
+    ///
+    /// // Removing the last tag clears the entity from the component
+    /// let state = state.apply( |state| tags::cmd::remove_tag(state, 100, "npc") ).unwrap();
+    /// assert!( state.tags.is_empty() );
+    /// ```
+    pub fn remove_tag(mut state: State, pub_id: PubId, tag: &Tag) -> CmdResult<State> {
+        if !qry::has_tag(&state, pub_id, tag) {
+            return Err("Can not remove a tag that isn't set on the entity".to_string());
+        }
+
+        let id = entity::qry::id(&state, pub_id);
+        let mut tags = state.tags.get_or_default(id);
+        tags.retain(|existing| existing != tag);
+
+        if tags.is_empty() {
+            state.tags.delete(id)?;
+            return Ok(state);
+        }
+
+        state.tags.update(id, tags)?;
+        Ok(state)
+    }
+}
+
+/// ## Tags > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Check if an entity has a `Tag`
+    /// See `tags::cmd::remove_tag` for tests
+    pub fn has_tag(state: &State, pub_id: PubId, tag: &Tag) -> bool {
+        tags(state, pub_id).iter().any(|existing| existing == tag)
+    }
+
+    /// QUERY > Get the tags of an entity in the order they were added
+    /// See `tags::cmd::add_tag` for tests
+    pub fn tags(state: &State, pub_id: PubId) -> Vec<String> {
+        let id = entity::qry::id(state, pub_id);
+        state.tags.get_or_default(id)
+    }
+
+    /// QUERY > Get the Public Ids of every entity with a `Tag`, in ascending order
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(300, "Goblin".into()) )
+    ///     .apply( Character::Add(100, "Dragon".into()) )
+    ///     .apply( Character::Add(200, "Bob".into()) )
+    ///     .apply( |state| tags::cmd::add_tag(state, 300, "minion") )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "boss") )
+    ///     .apply( |state| tags::cmd::add_tag(state, 200, "minion") )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(tags::qry::all_with_tag(&state, "minion"), vec![200, 300]);
+    /// assert_eq!(tags::qry::all_with_tag(&state, "boss"), vec![100]);
+    /// assert_eq!(tags::qry::all_with_tag(&state, "npc"), Vec::<PubId>::new());
+    /// ```
+    pub fn all_with_tag(state: &State, tag: &Tag) -> Vec<PubId> {
+        let ids: Vec<Id> = state.tags
+            .iter()
+            .filter(|(_, tags)| tags.iter().any(|existing| existing == tag))
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut pub_ids = entity::qry::pub_ids(state, ids);
+        pub_ids.sort();
+        pub_ids
+    }
+}
@@ -36,11 +36,75 @@ pub mod cmd {
     use super::*;
 
     /// COMMAND > Increment an entity's turn count
-    pub fn count(mut state: State, pub_id: PubId) -> CmdResult<State> {
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( |state| turn_count::cmd::set(state, 100, 254) )
+    ///     .apply( |state| turn_count::cmd::count(state, 100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!( turn_count::qry::count(&state, 100), 255);
+    ///
+    /// // Counting past the largest turn count is an error, even through sequenced play
+    /// assert!( state.clone().apply( |state| turn_count::cmd::count(state, 100) ).is_err() );
+    /// assert!( state
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .is_err() );
+    /// ```
+    pub fn count(state: State, pub_id: PubId) -> CmdResult<State> {
+        let key = turn_state::qry::key(&state, pub_id);
+        count_key(state, key)
+    }
+
+    /// COMMAND > Increment an entity's turn count within a specific scenario
+    /// See `seq_play::cmd::next_turn` for tests
+    pub fn count_in(state: State, scenario_pub_id: PubId, pub_id: PubId) -> CmdResult<State> {
+        let key = turn_state::qry::key_in(&state, scenario_pub_id, pub_id);
+        count_key(state, key)
+    }
+
+    fn count_key(mut state: State, key: TurnKey) -> CmdResult<State> {
+        let count: TurnCount = state.turn_count
+            .get_or_default(key)
+            .checked_add(1)
+            .ok_or("Turn count overflowed")?;
+        state.turn_count.update(key, count)?;
+        Ok(state)
+    }
 
-        let id = entity::qry::id(&state, pub_id);
-        let count: TurnCount = qry::count(&state,pub_id) + 1;
-        state.turn_count.update(id, count)?;
+    /// COMMAND > Set an entity's turn count to a specific value
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply( |state| turn_count::cmd::set(state, 100, 5) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!( turn_count::qry::count(&state, 100), 5);
+    ///
+    /// let state = state
+    ///     .apply( |state| turn_count::cmd::reset(state, 100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!( turn_count::qry::count(&state, 100), 0);
+    /// assert!( state.turn_count.is_empty() );
+    ///
+    /// // The entity must exist
+    /// assert!( state.apply( |state| turn_count::cmd::set(state, 200, 5) ).is_err() );
+    /// ```
+    pub fn set(mut state: State, pub_id: PubId, count: TurnCount) -> CmdResult<State> {
+        if !entity::qry::exists(&state, pub_id) {
+            return Err("Can not set the turn count of a non existent entity".to_string());
+        }
+        let key = turn_state::qry::key(&state, pub_id);
+        state.turn_count.update(key, count)?;
         Ok(state)
     }
 
@@ -57,8 +121,8 @@ pub mod cmd {
     /// assert_eq!( turn_count::qry::count(&state, 100), 0);
     /// ```
     pub fn reset(mut state: State, pub_id: PubId) -> CmdResult<State> {
-        let id = entity::qry::id(&state, pub_id);
-        state.turn_count.delete(id)?;
+        let key = turn_state::qry::key(&state, pub_id);
+        state.turn_count.delete(key)?;
         Ok(state)
     }
 
@@ -79,7 +143,20 @@ pub mod qry {
     /// assert_eq!( turn_count::qry::count(&state, 100), 1);
     /// ```
     pub fn count(state: &State, pub_id: PubId) -> TurnCount {
-        let id = entity::qry::id(state, pub_id);
-        state.turn_count.get(id).unwrap_or(0) as TurnCount
+        state.turn_count.get_or_default(turn_state::qry::key(state, pub_id))
+    }
+
+    /// QUERY > Get the turn count of an entity within a specific scenario
+    pub fn count_in(state: &State, scenario_pub_id: PubId, pub_id: PubId) -> TurnCount {
+        state.turn_count.get_or_default(turn_state::qry::key_in(state, scenario_pub_id, pub_id))
+    }
+
+    /// QUERY > Get the sum of the turn counts of every turn in a scenario
+    /// See `seq_play::cmd::next_turn` for tests
+    pub fn total(state: &State, scenario_pub_id: PubId) -> usize {
+        turn_order::qry::sequence(state, scenario_pub_id)
+            .into_iter()
+            .map(|turn| count_in(state, scenario_pub_id, turn) as usize)
+            .sum()
     }
 }
\ No newline at end of file
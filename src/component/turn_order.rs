@@ -2,10 +2,13 @@
 /// Todo - Add "Supported EntityTypes"
 
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 
+/// Turn orders are stored as internal Ids. Commands take and queries return
+/// Public Ids, converting at the boundary.
 pub type TurnOrder = Vec<Id>;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum TurnPosition {
     Start,
     Before(PubId),
@@ -24,34 +27,119 @@ pub mod cmd {
     /// let scenario_pub_id = 100;
     /// let state = State::default()
     ///         .apply( Scenario::Add(scenario_pub_id) )
-    ///         .apply( Character::Add(200, "ACharacter") )
-    ///         .apply( Character::Add(300, "BCharacter") )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 200))
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 300))
     ///         .unwrap();
     ///
     /// assert_eq!(turn_order::qry::sequence(&state, scenario_pub_id), vec![200, 300] );
+    ///
+    /// // An entity can only have one turn in a sequence
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 200))
+    ///         .unwrap();
+    ///
+    /// let duplicate = state.clone()
+    ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 200));
+    ///
+    /// assert_eq!(duplicate, Err("Can not add turn, turn already exists in the sequence".to_string()));
+    /// assert_eq!(turn_order::qry::sequence(&state, scenario_pub_id).len(), 1);
     /// ```
     pub fn add_turn(mut state: State, scenario_pub_id: PubId, turn_entity_pub_id: PubId) -> CmdResult<State> {
-        if !scenario::qry::exists(&state, scenario_pub_id) {
-            return Err("Can not add turn to nonexistant or nonscenario entity".into());
-        }
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+        entity::grd::must_exist(&state, turn_entity_pub_id)?;
 
         if !qry::is_supported_turn_order_type(&state, turn_entity_pub_id) {
             return Err("Can not add a turn for an unsupported entity type".into());
         }
 
         let scenario_id = scenario::qry::id(&state, scenario_pub_id);
-        let mut sequence = state.turn_order.get(scenario_id).unwrap_or_default();
+        let mut sequence = state.turn_order.get_or_default(scenario_id);
         let entity_id = entity::qry::id(&state, turn_entity_pub_id);
 
+        if sequence.contains(&entity_id) {
+            return Err("Can not add turn, turn already exists in the sequence".into());
+        }
+
         sequence.push(entity_id);
 
         state.turn_order.update(scenario_id, sequence);
+        scope_turn(&mut state, scenario_id, entity_id)?;
 
         Ok(state)
     }
 
+    /// Move a turn state and count set before an entity had any turn, keyed by
+    /// scenario 0, to the scenario it now has a turn in so they aren't left stale
+    fn scope_turn(state: &mut State, scenario_id: Id, entity_id: Id) -> CmdResult<()> {
+        let unscoped = (0, entity_id);
+        let scoped = (scenario_id, entity_id);
+
+        if let Some(turn_state) = state.turn_state.get(unscoped) {
+            state.turn_state.delete(unscoped)?;
+            if !state.turn_state.is_set(scoped) {
+                state.turn_state.insert(scoped, turn_state)?;
+            }
+        }
+        if let Some(turn_count) = state.turn_count.get(unscoped) {
+            state.turn_count.delete(unscoped)?;
+            if !state.turn_count.is_set(scoped) {
+                state.turn_count.insert(scoped, turn_count)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// COMMAND > Insert a turn at a position in the order of turns for a scenario
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 300]))
+    ///         .unwrap();
+    ///
+    /// let insert = |position| state.clone()
+    ///     .apply( |state| turn_order::cmd::insert_turn(state, scenario_pub_id, 400, position))
+    ///     .map(|state| turn_order::qry::sequence(&state, scenario_pub_id));
+    ///
+    /// assert_eq!(insert(TurnPosition::Start), Ok(vec![400, 200, 300]));
+    /// assert_eq!(insert(TurnPosition::End), Ok(vec![200, 300, 400]));
+    /// assert_eq!(insert(TurnPosition::Before(300)), Ok(vec![200, 400, 300]));
+    /// assert_eq!(insert(TurnPosition::After(200)), Ok(vec![200, 400, 300]));
+    ///
+    /// // The anchor must be in the sequence
+    /// assert!(insert(TurnPosition::Before(500)).is_err());
+    ///
+    /// // Only supported entity types can be inserted into an existing scenario
+    /// let state = state
+    ///     .apply( Player::Add(500, "APlayer".into()) )
+    ///     .unwrap();
+    /// assert!( state.clone()
+    ///     .apply( |state| turn_order::cmd::insert_turn(state, scenario_pub_id, 500, TurnPosition::Start))
+    ///     .is_err() );
+    /// assert!( state
+    ///     .apply( |state| turn_order::cmd::insert_turn(state, 999, 400, TurnPosition::Start))
+    ///     .is_err() );
+    /// ```
+    pub fn insert_turn(
+        state: State,
+        scenario_pub_id: PubId,
+        turn_entity_pub_id: PubId,
+        position: TurnPosition,
+    ) -> CmdResult<State> {
+        add_turn(state, scenario_pub_id, turn_entity_pub_id)
+            .and_then(|state| move_turn(state, scenario_pub_id, turn_entity_pub_id, position))
+    }
+
     /// COMMAND > Remove turn
     /// ```
     /// use yourupnext::prelude::*;
@@ -59,8 +147,8 @@ pub mod cmd {
     /// let scenario_pub_id = 100;
     /// let state = State::default()
     ///         .apply( Scenario::Add(scenario_pub_id) )
-    ///         .apply( Character::Add(200, "ACharacter") )
-    ///         .apply( Character::Add(300, "BCharacter") )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 200))
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 300))
     ///         .apply( |state| turn_order::cmd::remove_turn(state, scenario_pub_id, 200))
@@ -69,16 +157,16 @@ pub mod cmd {
     /// assert_eq!(turn_order::qry::sequence(&state, scenario_pub_id), vec![300] );
     /// ```
     pub fn remove_turn(mut state: State, scenario_pub_id: PubId, turn_entity_pub_id: PubId) -> CmdResult<State> {
-        if !scenario::qry::exists(&state, scenario_pub_id) {
-            return Err("Can not remove turn for nonexistant or nonscenario entity".into());
-        }
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+        entity::grd::must_exist(&state, turn_entity_pub_id)?;
 
         if !qry::is_supported_turn_order_type(&state, turn_entity_pub_id) {
             return Err("Can not remove a turn for an unsupported entity type".into());
         }
 
         let scenario_id = scenario::qry::id(&state, scenario_pub_id);
-        let mut sequence = state.turn_order.get(scenario_id).unwrap_or_default();
+        let mut sequence = state.turn_order.get_or_default(scenario_id);
         let entity_id = entity::qry::id(&state, turn_entity_pub_id);
 
         if !sequence.contains(&entity_id) {
@@ -88,6 +176,11 @@ pub mod cmd {
         sequence.retain(|sequenced_id| sequenced_id != &entity_id);
         state.turn_order.update(scenario_id, sequence);
 
+        // The turn's state and count belong to this scenario, so they go with it
+        let key = (scenario_id, entity_id);
+        state.turn_state.retain(|turn_key, _| *turn_key != key);
+        state.turn_count.retain(|turn_key, _| *turn_key != key);
+
         Ok(state)
     }
 
@@ -99,13 +192,13 @@ pub mod cmd {
     ///
     /// let state = State::default()
     ///         .apply( Scenario::Add(scenario_pub_id) )
-    ///         .apply( Character::Add(200, "ACharacter") )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 200))
-    ///         .apply( Character::Add(300, "BCharacter") )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 300))
-    ///         .apply( Character::Add(400, "CCharacter") )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 400))
-    ///         .apply( Character::Add(500, "DCharacter") )
+    ///         .apply( Character::Add(500, "DCharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 500))
     ///         .unwrap();
     ///
@@ -167,6 +260,76 @@ pub mod cmd {
     ///     turn_order::qry::sequence(&state_c_after_d, scenario_pub_id),
     ///     vec![200, 300, 500, 400 ]
     /// );
+    ///
+    /// // Removing the moved turn shifts everything behind it, so the anchor
+    /// // is located only after the moved turn has been taken out.
+    /// let state_a_after_c = state.clone()
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 200, TurnPosition::After(400)) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_order::qry::sequence(&state_a_after_c, scenario_pub_id),
+    ///     vec![300, 400, 200, 500 ]
+    /// );
+    ///
+    /// let state_a_before_d = state.clone()
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 200, TurnPosition::Before(500)) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_order::qry::sequence(&state_a_before_d, scenario_pub_id),
+    ///     vec![300, 400, 200, 500 ]
+    /// );
+    ///
+    /// let state_d_before_b = state.clone()
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 500, TurnPosition::Before(300)) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_order::qry::sequence(&state_d_before_b, scenario_pub_id),
+    ///     vec![200, 500, 300, 400 ]
+    /// );
+    ///
+    /// let state_d_after_a = state.clone()
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 500, TurnPosition::After(200)) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_order::qry::sequence(&state_d_after_a, scenario_pub_id),
+    ///     vec![200, 500, 300, 400 ]
+    /// );
+    ///
+    /// // An anchor must be part of the scenario's sequence of turns, even if
+    /// // it exists as an entity elsewhere.
+    /// let state = state
+    ///     .apply( Scenario::Add(600) )
+    ///     .apply( Character::Add(700, "ECharacter".into()) )
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 600, 700))
+    ///     .unwrap();
+    ///
+    /// assert!( entity::qry::exists(&state, 700) );
+    /// assert!( state.clone()
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 400, TurnPosition::Before(700)) )
+    ///     .is_err() );
+    /// assert!( state.clone()
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 400, TurnPosition::After(700)) )
+    ///     .is_err() );
+    ///
+    /// // Moves are stored as internal ids, so the stored order and the
+    /// // public sequence stay in step after adding and moving turns.
+    /// let state = state
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 200, TurnPosition::End) )
+    ///     .apply(|state|turn_order::cmd::move_turn(state, scenario_pub_id, 500, TurnPosition::Start) )
+    ///     .unwrap();
+    ///
+    /// let sequence = turn_order::qry::sequence(&state, scenario_pub_id);
+    /// assert_eq!( sequence, vec![500, 300, 400, 200] );
+    ///
+    /// let scenario_id = entity::qry::id(&state, scenario_pub_id);
+    /// assert_eq!(
+    ///     state.turn_order.get(scenario_id).unwrap(),
+    ///     entity::qry::ids(&state, sequence)
+    /// );
     /// ```
     pub fn move_turn(
         mut state: State,
@@ -174,9 +337,9 @@ pub mod cmd {
         turn_entity_pub_id: PubId,
         position: TurnPosition,
     ) -> CmdResult<State> {
-        if !scenario::qry::exists(&state, scenario_pub_id) {
-            return Err("Can not move a turn in a nonexistant or nonscenario entity".into());
-        }
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+        entity::grd::must_exist(&state, turn_entity_pub_id)?;
 
         if !qry::is_supported_turn_order_type(&state, turn_entity_pub_id) {
             return Err("Can not move a turn for an unsupported entity type".into());
@@ -186,64 +349,300 @@ pub mod cmd {
             return Err("You can not move an entity's turn that doesn't exist in a sequence of turns".into());
         }
 
-        let mut sequence = qry::sequence(&state, scenario_pub_id);
+        match position {
+            TurnPosition::Before(anchor_entity_pub_id) | TurnPosition::After(anchor_entity_pub_id)
+                if !qry::contains(&state, scenario_pub_id, anchor_entity_pub_id) => {
+                return Err("Can not move a turn relative to an anchor entity that isn't in the scenario's sequence of turns".into());
+            }
+            _ => {}
+        }
+
+        let scenario_id = scenario::qry::id(&state, scenario_pub_id);
+        let turn_entity_id = entity::qry::id(&state, turn_entity_pub_id);
+        let mut sequence = state.turn_order.get_or_default(scenario_id);
 
         if sequence.len() == 1 {
             return Ok(state);
         }
 
-        let turn_entity_index = match sequence.iter().position(|&x| x == turn_entity_pub_id) {
+        let turn_entity_index = match sequence.iter().position(|&x| x == turn_entity_id) {
             Some(index) => index,
             None => return Err("Unable to find the index of the entity you were trying to move".into())
         };
 
         sequence.remove(turn_entity_index);
 
-        let sequence = match position {
-            TurnPosition::Start => {
-                sequence.insert(0, turn_entity_pub_id);
-                sequence
-            }
-
-            TurnPosition::End => {
-                sequence.push(turn_entity_pub_id);
-                sequence
-            }
-
-            TurnPosition::Before(anchor_entity_pub_id) => {
+        let insert_index = match position {
+            TurnPosition::Start => 0,
+            TurnPosition::End => sequence.len(),
+            TurnPosition::Before(anchor_entity_pub_id) | TurnPosition::After(anchor_entity_pub_id) => {
                 if anchor_entity_pub_id == turn_entity_pub_id {
                     return Err("Can not move entity in turn order relative to itself".into());
                 }
-                let anchor_entity_id = match sequence.iter().position(|&x| x == anchor_entity_pub_id) {
+                let anchor_entity_id = entity::qry::id(&state, anchor_entity_pub_id);
+                let anchor_index = match sequence.iter().position(|&x| x == anchor_entity_id) {
                     Some(index) => index,
                     None => return Err("Unable to find anchor entity to place a turn before or after".into())
                 };
-                sequence.insert(anchor_entity_id, turn_entity_pub_id);
-                sequence
-            }
-
-            TurnPosition::After(anchor_entity_pub_id) => {
-                if anchor_entity_pub_id == turn_entity_pub_id {
-                    return Err("Can not move entity in turn order relative to itself".into());
+                match position {
+                    TurnPosition::After(_) => anchor_index + 1,
+                    _ => anchor_index,
                 }
-                let anchor_entity_id = match sequence.iter().position(|&x| x == anchor_entity_pub_id) {
-                    Some(index) => index + 1,
-                    None => return Err("Unable to find anchor entity to place a turn before or after".into())
-                };
-                sequence.insert(anchor_entity_id, turn_entity_pub_id);
-                sequence
             }
         };
 
-        set(state, scenario_pub_id, sequence)
+        sequence.insert(insert_index, turn_entity_id);
+        state.turn_order.update(scenario_id, sequence);
+
+        Ok(state)
+    }
+
+    /// COMMAND > Reverse the sequence of turns in a scenario
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 300, 400]))
+    ///         .apply( |state| turn_order::cmd::reverse(state, scenario_pub_id))
+    ///         .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, scenario_pub_id), vec![400, 300, 200] );
+    ///
+    /// // The scenario must exist
+    /// assert!( state.apply( |state| turn_order::cmd::reverse(state, 999) ).is_err() );
+    /// ```
+    pub fn reverse(mut state: State, scenario_pub_id: PubId) -> CmdResult<State> {
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+
+        let scenario_id = scenario::qry::id(&state, scenario_pub_id);
+        let mut sequence = state.turn_order.get_or_default(scenario_id);
+
+        sequence.reverse();
+        state.turn_order.update(scenario_id, sequence);
+
+        Ok(state)
+    }
+
+    /// COMMAND > Rotate the sequence of turns in a scenario
+    ///
+    /// Positive steps move turns from the start to the end, negative steps
+    /// move turns from the end to the start. Relative order is kept.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 300, 400]))
+    ///         .unwrap();
+    ///
+    /// let rotate = |steps| state.clone()
+    ///     .apply( |state| turn_order::cmd::rotate(state, scenario_pub_id, steps))
+    ///     .map(|state| turn_order::qry::sequence(&state, scenario_pub_id));
+    ///
+    /// assert_eq!(rotate(1), Ok(vec![300, 400, 200]));
+    /// assert_eq!(rotate(-1), Ok(vec![400, 200, 300]));
+    /// assert_eq!(rotate(3), Ok(vec![200, 300, 400]));
+    /// assert_eq!(rotate(-4), Ok(vec![400, 200, 300]));
+    ///
+    /// // The scenario must exist
+    /// assert!( state.apply( |state| turn_order::cmd::rotate(state, 999, 1) ).is_err() );
+    /// ```
+    pub fn rotate(mut state: State, scenario_pub_id: PubId, steps: i32) -> CmdResult<State> {
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+
+        let scenario_id = scenario::qry::id(&state, scenario_pub_id);
+        let mut sequence = state.turn_order.get_or_default(scenario_id);
+
+        if sequence.is_empty() {
+            return Ok(state);
+        }
+
+        let steps = steps.rem_euclid(sequence.len() as i32) as usize;
+        sequence.rotate_left(steps);
+        state.turn_order.update(scenario_id, sequence);
+
+        Ok(state)
+    }
+
+    /// COMMAND > Order a sequence of turns by initiative, highest first
+    ///
+    /// Turns are taken in their current sequence order and stably sorted, so
+    /// turns sharing an initiative keep their relative order. Turns without an
+    /// initiative are placed last.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
+    ///         .apply( Character::Add(500, "DCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 300, 400, 500]))
+    ///         .apply( |state| initiative::cmd::set(state, 200, 8) )
+    ///         .apply( |state| initiative::cmd::set(state, 300, 12) )
+    ///         .apply( |state| initiative::cmd::set(state, 400, 8) )
+    ///         .unwrap();
+    ///
+    /// for _ in 0..10 {
+    ///     let ordered = state.clone()
+    ///         .apply( |state| turn_order::cmd::order_by_initiative(state, scenario_pub_id) )
+    ///         .unwrap();
+    ///     assert_eq!(
+    ///         turn_order::qry::sequence(&ordered, scenario_pub_id),
+    ///         vec![300, 200, 400, 500]
+    ///     );
+    /// }
+    ///
+    /// // Ties keep the order they already had
+    /// let state = state
+    ///     .apply( |state| turn_order::cmd::move_turn(state, scenario_pub_id, 400, TurnPosition::Start) )
+    ///     .apply( |state| turn_order::cmd::order_by_initiative(state, scenario_pub_id) )
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     turn_order::qry::sequence(&state, scenario_pub_id),
+    ///     vec![300, 400, 200, 500]
+    /// );
+    ///
+    /// // The scenario must exist
+    /// assert!( state.apply( |state| turn_order::cmd::order_by_initiative(state, 999) ).is_err() );
+    /// ```
+    pub fn order_by_initiative(state: State, scenario_pub_id: PubId) -> CmdResult<State> {
+        sort_turns_descending(state, scenario_pub_id, |state, id| state.initiative.get(id))
+    }
+
+    /// COMMAND > Order a sequence of turns by initiative, breaking ties with a stat
+    ///
+    /// When initiatives tie, the turn with the higher value for `stat_name`
+    /// goes first. Turns that are still tied keep their sequence order.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
+    ///         .apply( Character::Add(500, "DCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 300, 400, 500]))
+    ///         .apply( |state| initiative::cmd::set(state, 200, 10) )
+    ///         .apply( |state| initiative::cmd::set(state, 300, 10) )
+    ///         .apply( |state| initiative::cmd::set(state, 400, 12) )
+    ///         .apply( |state| initiative::cmd::set(state, 500, 10) )
+    ///         .apply( |state| stat::cmd::set_stat(state, 200, "dex", 11) )
+    ///         .apply( |state| stat::cmd::set_stat(state, 300, "dex", 16) )
+    ///         .apply( |state| stat::cmd::set_stat(state, 400, "dex", 1) )
+    ///         .apply( |state| stat::cmd::set_stat(state, 500, "dex", 11) )
+    ///         .apply( |state| turn_order::cmd::order_by_initiative_with_tiebreak(state, scenario_pub_id, "dex") )
+    ///         .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_order::qry::sequence(&state, scenario_pub_id),
+    ///     vec![400, 300, 200, 500]
+    /// );
+    /// ```
+    pub fn order_by_initiative_with_tiebreak(state: State, scenario_pub_id: PubId, stat_name: &str) -> CmdResult<State> {
+        sort_turns_descending(state, scenario_pub_id, |state, id| {
+            let tiebreak = state.stats.get_or_default(stat::qry::key(id, stat_name));
+            (state.initiative.get(id), tiebreak)
+        })
+    }
+
+    /// Stably sort a scenario's turns by a key, highest first
+    fn sort_turns_descending<K: Ord>(
+        mut state: State,
+        scenario_pub_id: PubId,
+        key: impl Fn(&State, Id) -> K,
+    ) -> CmdResult<State> {
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+
+        let scenario_id = scenario::qry::id(&state, scenario_pub_id);
+        let mut sequence = state.turn_order.get_or_default(scenario_id);
+
+        sequence.sort_by_key(|id| std::cmp::Reverse(key(&state, *id)));
+        state.turn_order.update(scenario_id, sequence);
+
+        Ok(state)
     }
 
     /// COMMAND > Set (add multiple and replace)
     ///
-    pub fn set(mut state: State, scenario_pub_id: PubId, turn_order: TurnOrder) -> CmdResult<State> {
+    /// A turn order may only contain each entity once and every entity must be
+    /// a supported turn order type.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Player::Add(400, "APlayer".into()) )
+    ///         .unwrap();
+    ///
+    /// let clean_state = state.clone()
+    ///     .apply(|state| turn_order::cmd::set(state, scenario_pub_id, vec![300, 200]))
+    ///     .unwrap();
+    /// assert_eq!(turn_order::qry::sequence(&clean_state, scenario_pub_id), vec![300, 200] );
+    ///
+    /// // Duplicate entities are rejected
+    /// assert!( state.clone()
+    ///     .apply(|state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 300, 200]))
+    ///     .is_err() );
+    ///
+    /// // Unsupported entity types are rejected
+    /// assert!( state.clone()
+    ///     .apply(|state| turn_order::cmd::set(state, scenario_pub_id, vec![200, 400]))
+    ///     .is_err() );
+    ///
+    /// // The scenario must exist
+    /// assert!( state.clone()
+    ///     .apply(|state| turn_order::cmd::set(state, 999, vec![200, 300]))
+    ///     .is_err() );
+    /// ```
+    pub fn set(mut state: State, scenario_pub_id: PubId, turn_order: Vec<PubId>) -> CmdResult<State> {
+        entity::grd::must_exist(&state, scenario_pub_id)?;
+        entity_type::grd::must_be(&state, scenario_pub_id, EntityType::Scenario)?;
+
+        for (index, turn_entity_pub_id) in turn_order.iter().enumerate() {
+            if turn_order[..index].contains(turn_entity_pub_id) {
+                return Err("Can not set a turn order that contains the same entity more than once".into());
+            }
+            if !qry::is_supported_turn_order_type(&state, *turn_entity_pub_id) {
+                return Err("Can not set a turn order that contains an unsupported entity type".into());
+            }
+        }
+
         let scenario_id = entity::qry::id(&state, scenario_pub_id);
         let turn_order_ids = entity::qry::ids(&state, turn_order);
-        state.turn_order.update(scenario_id, turn_order_ids);
+
+        // Drop the turn states and counts of turns that are no longer in the sequence
+        let dropped = |(turn_scenario_id, id): &TurnKey| *turn_scenario_id == scenario_id && !turn_order_ids.contains(id);
+        state.turn_state.retain(|key, _| !dropped(key));
+        state.turn_count.retain(|key, _| !dropped(key));
+
+        state.turn_order.update(scenario_id, turn_order_ids.clone());
+        for entity_id in turn_order_ids {
+            scope_turn(&mut state, scenario_id, entity_id)?;
+        }
         Ok(state)
     }
 
@@ -261,10 +660,10 @@ pub mod qry {
     /// let b_scenario_pub_id = 200;
     /// let state = State::default()
     ///         .apply( Scenario::Add(a_scenario_pub_id) )
-    ///         .apply( Character::Add(300, "ACharacter") )
+    ///         .apply( Character::Add(300, "ACharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, a_scenario_pub_id, 300))
     ///         .apply( Scenario::Add(b_scenario_pub_id) )
-    ///         .apply( Character::Add(400, "BCharacter") )
+    ///         .apply( Character::Add(400, "BCharacter".into()) )
     ///         .apply( |state| turn_order::cmd::add_turn(state, b_scenario_pub_id, 400))
     ///         .unwrap();
     ///
@@ -275,14 +674,89 @@ pub mod qry {
         sequence(state, scenario_pub_id).contains(&entity_pub_id)
     }
 
+    /// QUERY > Find the scenario whose sequence contains an entity's turn
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(100) )
+    ///         .apply( Character::Add(300, "ACharacter".into()) )
+    ///         .apply( Character::Add(400, "BCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::add_turn(state, 100, 300))
+    ///         .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::scenario_of(&state, 300), Some(100) );
+    /// assert_eq!(turn_order::qry::scenario_of(&state, 400), None );
+    /// ```
+    pub fn scenario_of(state: &State, entity_pub_id: PubId) -> Option<PubId> {
+        let entity_id = entity::qry::id(state, entity_pub_id);
+        if entity_id == 0 {
+            return None;
+        }
+        let scenario_id = state.turn_order
+            .iter()
+            .filter(|(_, sequence)| sequence.contains(&entity_id))
+            .map(|(scenario_id, _)| scenario_id)
+            .min()?;
+        entity::qry::pub_id(state, scenario_id)
+    }
+
     /// QUERY > Get the sequence of Public Ids that expresses the order of turns
     pub fn sequence(state: &State, scenario_pub_id: PubId) -> Vec<PubId> {
         let id = entity::qry::id(state, scenario_pub_id);
-        let ids = state.turn_order.get(id).unwrap_or_default();
+        let ids = state.turn_order.get_or_default(id);
         entity::qry::pub_ids(state, ids)
     }
 
 
+    /// QUERY > Get the names of the turns immediately before and after an entity's turn
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let scenario_pub_id = 100;
+    /// let state = State::default()
+    ///         .apply( Scenario::Add(scenario_pub_id) )
+    ///         .apply( Character::Add(200, "ACharacter".into()) )
+    ///         .apply( Character::Add(300, "BCharacter".into()) )
+    ///         .apply( Character::Add(400, "CCharacter".into()) )
+    ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 200))
+    ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 300))
+    ///         .apply( |state| turn_order::cmd::add_turn(state, scenario_pub_id, 400))
+    ///         .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_order::qry::neighbors_named(&state, scenario_pub_id, 300),
+    ///     (Some("ACharacter".to_string()), Some("CCharacter".to_string()))
+    /// );
+    /// assert_eq!(
+    ///     turn_order::qry::neighbors_named(&state, scenario_pub_id, 200),
+    ///     (None, Some("BCharacter".to_string()))
+    /// );
+    /// assert_eq!(
+    ///     turn_order::qry::neighbors_named(&state, scenario_pub_id, 500),
+    ///     (None, None)
+    /// );
+    /// ```
+    pub fn neighbors_named(state: &State, scenario_pub_id: PubId, entity_pub_id: PubId) -> (Option<String>, Option<String>) {
+        let sequence = sequence(state, scenario_pub_id);
+
+        let index = match sequence.iter().position(|&x| x == entity_pub_id) {
+            Some(index) => index,
+            None => return (None, None),
+        };
+
+        let previous = index
+            .checked_sub(1)
+            .and_then(|previous_index| sequence.get(previous_index))
+            .map(|previous| name::qry::get(state, *previous));
+
+        let next = sequence
+            .get(index + 1)
+            .map(|next| name::qry::get(state, *next));
+
+        (previous, next)
+    }
+
     /// QUERY > Get valid support types that can have a turn order
     pub fn is_supported_turn_order_type(state: &State, entity_pub_id: PubId) -> bool {
         match entity_type::qry::get(state, entity_pub_id) {
@@ -294,6 +768,7 @@ pub mod qry {
             EntityType::Location => true,
             EntityType::Effect => true,
             EntityType::Generic => true,
+            EntityType::Custom(_) => true,
         }
     }
 }
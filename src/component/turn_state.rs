@@ -5,9 +5,15 @@
 ///
 
 use crate::prelude::*;
+use serde::Serialize;
 
+/// Turn states (and turn counts) are kept per scenario so that an entity
+/// with turns in two scenarios has an independent status in each. A key is
+/// `(scenario Id, entity Id)`; entities without a turn in any scenario,
+/// including scenarios themselves, use a scenario Id of 0.
+pub type TurnKey = (Id, Id);
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum TurnStatus {
     Free,
     Available,
@@ -19,6 +25,33 @@ pub enum TurnStatus {
     None
 }
 
+impl TurnStatus {
+    /// Check if a turn can legally move from this status to another.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// assert!(TurnStatus::Available.can_transition_to(TurnStatus::Active));
+    /// assert!(TurnStatus::Active.can_transition_to(TurnStatus::Held(1)));
+    /// assert!(!TurnStatus::Completed.can_transition_to(TurnStatus::Active));
+    /// ```
+    pub fn can_transition_to(self, to: TurnStatus) -> bool {
+        matches!(
+            (self, to),
+            (TurnStatus::Free, TurnStatus::Available)
+                | (TurnStatus::Available, TurnStatus::Free)
+                | (TurnStatus::Available, TurnStatus::Active)
+                | (TurnStatus::Available, TurnStatus::Skipped)
+                | (TurnStatus::Active, TurnStatus::Completed)
+                | (TurnStatus::Active, TurnStatus::Paused)
+                | (TurnStatus::Active, TurnStatus::Held(_))
+                | (TurnStatus::Paused, TurnStatus::Active)
+                | (TurnStatus::Held(_), TurnStatus::Active)
+                | (TurnStatus::Completed, TurnStatus::Available)
+                | (TurnStatus::Skipped, TurnStatus::Available)
+        )
+    }
+}
+
 pub mod cmd {
     use super::*;
 
@@ -40,33 +73,245 @@ pub mod cmd {
     /// assert_eq!( turn_state::qry::get(&state, 100), TurnStatus::None );
     /// assert!( state.turn_state.is_empty() );
     /// ```
-    pub fn set(mut state: State, pub_id: PubId, turn_state: TurnStatus) -> CmdResult<State> {
+    pub fn set(state: State, pub_id: PubId, turn_state: TurnStatus) -> CmdResult<State> {
+        let key = qry::key(&state, pub_id);
+        set_key(state, key, turn_state)
+    }
 
-        let id = entity::qry::id(&state, pub_id);
+    /// COMMAND > Set the state of an entity's turn within a specific scenario
+    ///
+    /// An entity with turns in more than one scenario has a separate turn
+    /// state in each. `turn_state::cmd::set` uses the first scenario (by
+    /// internal Id) that has the entity's turn.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Scenario::Add(200) )
+    ///     .apply( Character::Add(300, "ACharacter".into()) )
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 100, 300) )
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 200, 300) )
+    ///     .apply( |state| turn_state::cmd::set_in(state, 100, 300, TurnStatus::Active) )
+    ///     .apply( |state| turn_state::cmd::set_in(state, 200, 300, TurnStatus::Held(1)) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get_in(&state, 100, 300), TurnStatus::Active);
+    /// assert_eq!(turn_state::qry::get_in(&state, 200, 300), TurnStatus::Held(1));
+    /// assert_eq!(turn_state::qry::get(&state, 300), TurnStatus::Active);
+    ///
+    /// // Changing one scenario's turn leaves the other alone
+    /// let state = state
+    ///     .apply( |state| turn_state::cmd::set_in(state, 100, 300, TurnStatus::Completed) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get_in(&state, 100, 300), TurnStatus::Completed);
+    /// assert_eq!(turn_state::qry::get_in(&state, 200, 300), TurnStatus::Held(1));
+    ///
+    /// // Removing a turn clears its state in that scenario only
+    /// let state = state
+    ///     .apply( |state| turn_order::cmd::remove_turn(state, 100, 300) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get_in(&state, 100, 300), TurnStatus::None);
+    /// assert_eq!(turn_state::qry::get(&state, 300), TurnStatus::Held(1));
+    ///
+    /// // The entity must have a turn in the scenario
+    /// assert!( state.apply( |state| turn_state::cmd::set_in(state, 100, 300, TurnStatus::Active) ).is_err() );
+    /// ```
+    pub fn set_in(state: State, scenario_pub_id: PubId, pub_id: PubId, turn_state: TurnStatus) -> CmdResult<State> {
+        if !turn_order::qry::contains(&state, scenario_pub_id, pub_id) {
+            return Err("Can not set the turn state of an entity without a turn in the scenario".to_string());
+        }
+        let key = qry::key_in(&state, scenario_pub_id, pub_id);
+        set_key(state, key, turn_state)
+    }
 
+    fn set_key(mut state: State, key: TurnKey, turn_state: TurnStatus) -> CmdResult<State> {
         // None types shouldn't be stored
         if turn_state == TurnStatus::None {
-            state.turn_state.delete(id);
+            state.turn_state.delete(key);
             return Ok(state);
         }
 
-        state.turn_state.update(id, turn_state)?;
+        state.turn_state.update(key, turn_state)?;
         Ok(state)
     }
+
+    /// COMMAND > Move an entity's turn to a new status, erroring on illegal transitions.
+    /// Use `set` to change the status without checking the transition.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply(|state| turn_state::cmd::set(state, 100, TurnStatus::Available))
+    ///     .apply(|state| turn_state::cmd::transition(state, 100, TurnStatus::Active))
+    ///     .apply(|state| turn_state::cmd::transition(state, 100, TurnStatus::Held(1)))
+    ///     .apply(|state| turn_state::cmd::transition(state, 100, TurnStatus::Active))
+    ///     .apply(|state| turn_state::cmd::transition(state, 100, TurnStatus::Completed))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Completed);
+    ///
+    /// let state = state.apply(|state| turn_state::cmd::transition(state, 100, TurnStatus::Active));
+    /// assert!(state.is_err());
+    /// ```
+    pub fn transition(state: State, pub_id: PubId, to: TurnStatus) -> CmdResult<State> {
+        let from = qry::get(&state, pub_id);
+        if !from.can_transition_to(to) {
+            return Err(format!("Can not transition a turn from {:?} to {:?}", from, to));
+        }
+        set(state, pub_id, to)
+    }
 }
 
 pub mod qry {
     use super::*;
 
+    /// QUERY > Get the key of an entity's turn state. The scenario is the first
+    /// (by internal Id) that has the entity's turn, or 0 when there isn't one.
+    ///
+    /// An entity with turns in several scenarios resolves to the one with the
+    /// lowest internal Id; use `key_in` to pick a scenario. A turn state set
+    /// before an entity had any turn moves to its first scenario when the turn
+    /// is added.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( |state| turn_state::cmd::set(state, 100, TurnStatus::Held(2)) )
+    ///     .apply( |state| turn_count::cmd::count(state, 100) )
+    ///     .unwrap();
+    ///
+    /// let id = entity::qry::id(&state, 100);
+    /// let scenario_id = entity::qry::id(&state, 50);
+    /// assert_eq!(turn_state::qry::key(&state, 100), (0, id));
+    ///
+    /// let state = state
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 50, 100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::key(&state, 100), (scenario_id, id));
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Held(2));
+    /// assert_eq!(turn_count::qry::count(&state, 100), 1);
+    /// assert!( !state.turn_state.is_set((0, id)) );
+    /// assert!( !state.turn_count.is_set((0, id)) );
+    /// ```
+    pub fn key(state: &State, pub_id: PubId) -> TurnKey {
+        let id = entity::qry::id(state, pub_id);
+        let scenario_id = state.turn_order
+            .iter()
+            .filter(|(_, sequence)| sequence.contains(&id))
+            .map(|(scenario_id, _)| scenario_id)
+            .min()
+            .unwrap_or(0);
+        (scenario_id, id)
+    }
+
+    /// QUERY > Get the key of an entity's turn state within a specific scenario
+    pub fn key_in(state: &State, scenario_pub_id: PubId, pub_id: PubId) -> TurnKey {
+        (entity::qry::id(state, scenario_pub_id), entity::qry::id(state, pub_id))
+    }
+
     /// QUERY > Get the state of an entity's turn
     pub fn get(state: &State, pub_id: PubId) -> TurnStatus {
-        let id = entity::qry::id(state, pub_id);
+        get_key(state, key(state, pub_id))
+    }
+
+    /// QUERY > Get the state of an entity's turn within a specific scenario
+    /// See `turn_state::cmd::set_in` for tests
+    pub fn get_in(state: &State, scenario_pub_id: PubId, pub_id: PubId) -> TurnStatus {
+        get_key(state, key_in(state, scenario_pub_id, pub_id))
+    }
+
+    fn get_key(state: &State, key: TurnKey) -> TurnStatus {
+        let (_, id) = key;
         if !state.registry.has_id(&id) || id == 0 {
             return TurnStatus::None;
         }
-        match state.turn_state.get(id) {
+        match state.turn_state.get(key) {
             Some(turn_state) => turn_state,
             _ => TurnStatus::None
         }
     }
-}
\ No newline at end of file
+
+    /// QUERY > Get the recent turn states of an entity, oldest first
+    ///
+    /// Only recorded when history is enabled with `State::track_history`.
+    /// Clearing a turn state with `TurnStatus::None` forgets its history.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .track_history(2)
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     turn_state::qry::history(&state, 100),
+    ///     vec![TurnStatus::Available, TurnStatus::Active]
+    /// );
+    ///
+    /// // A removed and re-added turn doesn't inherit the old history
+    /// let state = state
+    ///     .apply( SeqPlay::RemoveTurn(100) )
+    ///     .apply( |state| turn_state::cmd::set(state, 100, TurnStatus::Available) )
+    ///     .apply( |state| turn_state::cmd::set(state, 100, TurnStatus::None) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::history(&state, 100), Vec::<TurnStatus>::new());
+    /// ```
+    pub fn history(state: &State, pub_id: PubId) -> Vec<TurnStatus> {
+        state.turn_state.history(key(state, pub_id))
+    }
+
+    /// QUERY > Get the turn entities in a scenario with a specific status, in turn order.
+    /// `Held(n)` only matches the same `n`, see `all_held` to match any held turn.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply_with(
+    ///         vec![(200, "A"), (300, "B"), (400, "C"), (500, "D")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     )
+    ///     .apply_with(vec![200, 300, 400, 500], |character_id| SeqPlay::AddTurn(100, character_id))
+    ///     .apply(|state| turn_state::cmd::set(state, 200, TurnStatus::Available))
+    ///     .apply(|state| turn_state::cmd::set(state, 300, TurnStatus::Completed))
+    ///     .apply(|state| turn_state::cmd::set(state, 400, TurnStatus::Available))
+    ///     .apply(|state| turn_state::cmd::set(state, 500, TurnStatus::Held(2)))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::all_in(&state, 100, TurnStatus::Available), vec![200, 400]);
+    /// assert_eq!(turn_state::qry::all_in(&state, 100, TurnStatus::Completed), vec![300]);
+    /// assert_eq!(turn_state::qry::all_in(&state, 100, TurnStatus::Held(2)), vec![500]);
+    /// assert_eq!(turn_state::qry::all_in(&state, 100, TurnStatus::Held(1)), Vec::<PubId>::new());
+    /// assert_eq!(turn_state::qry::all_held(&state, 100), vec![500]);
+    /// ```
+    pub fn all_in(state: &State, scenario_pub_id: PubId, status: TurnStatus) -> Vec<PubId> {
+        turn_order::qry::sequence(state, scenario_pub_id)
+            .into_iter()
+            .filter(|pub_id| get_in(state, scenario_pub_id, *pub_id) == status)
+            .collect()
+    }
+
+    /// QUERY > Get the turn entities in a scenario that are held, regardless of hold count
+    /// See `turn_state::qry::all_in` for tests
+    pub fn all_held(state: &State, scenario_pub_id: PubId) -> Vec<PubId> {
+        turn_order::qry::sequence(state, scenario_pub_id)
+            .into_iter()
+            .filter(|pub_id| matches!(get_in(state, scenario_pub_id, *pub_id), TurnStatus::Held(_)))
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+//! # Dice
+//! Random number generation for rolls. Rolls go through the `Rng` trait so
+//! that callers can inject their own source of randomness; `SeededRng` is a
+//! small deterministic generator so the same seed always rolls the same values.
+
+/// A source of random numbers
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    /// Roll a die with `sides` sides, giving a value in `1..=sides`
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let mut rng = SeededRng::new(42);
+    /// for _ in 0..100 {
+    ///     let rolled = rng.roll(20);
+    ///     assert!( (1..=20).contains(&rolled) );
+    /// }
+    /// ```
+    fn roll(&mut self, sides: u8) -> i32 {
+        if sides == 0 {
+            return 0;
+        }
+        (self.next_u64() % sides as u64) as i32 + 1
+    }
+}
+
+/// A seedable SplitMix64 generator
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let mut a = SeededRng::new(7);
+/// let mut b = SeededRng::new(7);
+/// assert_eq!( a.next_u64(), b.next_u64() );
+/// assert_eq!( a.roll(20), b.roll(20) );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+}
+
+impl Rng for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,152 @@
+/// # Domain Events
+/// Events describe what changed when a command was applied so that a UI
+/// or network layer can react to it. Commands stay `State -> State`; the
+/// events are found by comparing the state before and after a command.
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DomainEvent {
+    EntityAdded(EntityId),
+    EntityRemoved(EntityId),
+    Renamed(EntityId),
+    TurnAdded(ScenarioId, EntityId),
+    RoundAdvanced(ScenarioId),
+    TurnAdvanced(ScenarioId, EntityId),
+}
+
+/// QUERY > List the events that happened between two states
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let old = State::default()
+///     .apply( Scenario::Add(100) )
+///     .unwrap();
+///
+/// let new = old.clone()
+///     .apply( Character::Add(200, "ACharacter".into()) )
+///     .unwrap();
+///
+/// assert_eq!(event::events(&old, &new), vec![DomainEvent::EntityAdded(200)]);
+/// assert!(event::events(&new, &new).is_empty());
+/// ```
+pub fn events(old: &State, new: &State) -> Vec<DomainEvent> {
+    let mut events = vec![];
+
+    events.extend(
+        entity::qry::all(new)
+            .into_iter()
+            .filter(|pub_id| !entity::qry::exists(old, *pub_id))
+            .map(DomainEvent::EntityAdded)
+    );
+
+    events.extend(
+        entity::qry::all(old)
+            .into_iter()
+            .filter(|pub_id| !entity::qry::exists(new, *pub_id))
+            .map(DomainEvent::EntityRemoved)
+    );
+
+    events.extend(
+        state::diff(old, new).name.changed
+            .into_iter()
+            .map(DomainEvent::Renamed)
+    );
+
+    let scenarios = entity::qry::all(new)
+        .into_iter()
+        .filter(|pub_id| entity_type::qry::is(new, *pub_id, EntityType::Scenario));
+
+    for scenario_pub_id in scenarios {
+        let old_sequence = turn_order::qry::sequence(old, scenario_pub_id);
+        let new_sequence = turn_order::qry::sequence(new, scenario_pub_id);
+
+        events.extend(
+            new_sequence
+                .iter()
+                .filter(|pub_id| !old_sequence.contains(pub_id))
+                .map(|pub_id| DomainEvent::TurnAdded(scenario_pub_id, *pub_id))
+        );
+
+        if round_advanced(old, new, scenario_pub_id, &new_sequence) {
+            events.push(DomainEvent::RoundAdvanced(scenario_pub_id));
+        }
+
+        events.extend(
+            new_sequence
+                .iter()
+                .filter(|pub_id| turn_state::qry::get(new, **pub_id) == TurnStatus::Active)
+                .filter(|pub_id| turn_state::qry::get(old, **pub_id) != TurnStatus::Active)
+                .map(|pub_id| DomainEvent::TurnAdvanced(scenario_pub_id, *pub_id))
+        );
+    }
+
+    events
+}
+
+/// A round has advanced when a scenario in sequenced play has finished turns
+/// made available again, leaving no finished turns behind.
+fn round_advanced(old: &State, new: &State, scenario_pub_id: ScenarioId, sequence: &[EntityId]) -> bool {
+    let is_finished = |state: &State, pub_id: EntityId| matches!(
+        turn_state::qry::get(state, pub_id),
+        TurnStatus::Completed | TurnStatus::Skipped
+    );
+
+    turn_state::qry::get(new, scenario_pub_id) == TurnStatus::Active
+        && sequence.iter().any(|pub_id| {
+            is_finished(old, *pub_id) && turn_state::qry::get(new, *pub_id) == TurnStatus::Available
+        })
+        && !sequence.iter().any(|pub_id| is_finished(new, *pub_id))
+}
+
+impl State {
+    /// Apply a command and collect the events it produced
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( SeqPlay::AddTurn(100, 200) )
+    ///     .apply( SeqPlay::AddTurn(100, 300) )
+    ///     .apply( SeqPlay::Enable(100) )
+    ///     .unwrap();
+    ///
+    /// let (state, events) = state.apply_events( SeqPlay::NextTurn(100) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::TurnAdvanced(100, 200)]);
+    ///
+    /// let (state, events) = state.apply_events( SeqPlay::NextTurn(100) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::TurnAdvanced(100, 300)]);
+    ///
+    /// let (state, _) = state.apply_events( SeqPlay::NextTurn(100) ).unwrap();
+    /// let (state, events) = state.apply_events( SeqPlay::NextRound(100) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::RoundAdvanced(100)]);
+    ///
+    /// // Adding a character yields exactly one event
+    /// let (state, events) = state.apply_events( Character::Add(400, "CCharacter".into()) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::EntityAdded(400)]);
+    ///
+    /// let (state, events) = state.apply_events( SeqPlay::AddTurn(100, 400) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::TurnAdded(100, 400)]);
+    ///
+    /// let (state, events) = state.apply_events( Character::Rename(400, "Renamed".into()) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::Renamed(400)]);
+    ///
+    /// let (state, events) = state.apply_events( Character::Remove(400) ).unwrap();
+    /// assert_eq!(events, vec![DomainEvent::EntityRemoved(400)]);
+    ///
+    /// // Failed commands produce an error rather than events
+    /// assert!(state.apply_events( SeqPlay::NextTurn(999) ).is_err());
+    /// ```
+    pub fn apply_events<T: Applicable>(self, command: T) -> CmdResult<(State, Vec<DomainEvent>)> {
+        let new = command.apply_to(self.clone())?;
+        let events = events(&self, &new);
+        Ok((new, events))
+    }
+
+    /// Apply a command and collect the events it produced. Use `State::apply_events`.
+    #[deprecated(note = "use apply_events")]
+    pub fn apply_with_events<T: Applicable>(self, command: T) -> CmdResult<(State, Vec<DomainEvent>)> {
+        self.apply_events(command)
+    }
+}
@@ -0,0 +1,85 @@
+/// # Journaled State
+/// A wrapper around `State` that records the `Debug` representation of
+/// each successfully applied command. The journal is useful for debugging
+/// and inspecting how a state was built.
+use crate::prelude::*;
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct JournaledState {
+    state: State,
+    journal: Vec<String>,
+}
+
+impl JournaledState {
+    /// Start a journal on an existing state. The journal starts empty.
+    pub fn new(state: State) -> Self {
+        JournaledState {
+            state,
+            journal: vec![],
+        }
+    }
+
+    /// Apply a command to the wrapped state, recording it in the journal.
+    /// Commands that fail are not recorded.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let journaled = JournaledState::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::AssignPlayer(200, 100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(journaled.journal().len(), 3);
+    /// assert_eq!(journaled.journal()[0], "Add(100, \"APlayer\")");
+    /// assert_eq!(journaled.journal()[2], "AssignPlayer(200, 100)");
+    /// assert_eq!(player::qry::characters_of(journaled.state(), 100), vec![200]);
+    ///
+    /// let journaled = journaled.clear_journal();
+    /// assert!(journaled.journal().is_empty());
+    /// assert!(character::qry::exists(journaled.state(), 200));
+    /// ```
+    pub fn apply<T: Applicable + Debug>(self, command: T) -> CmdResult<JournaledState> {
+        let entry = format!("{:?}", command);
+        let JournaledState { state, mut journal } = self;
+        let state = command.apply_to(state)?;
+        journal.push(entry);
+        Ok(JournaledState { state, journal })
+    }
+
+    /// The `Debug` representation of each applied command, in order
+    pub fn journal(&self) -> &Vec<String> {
+        &self.journal
+    }
+
+    /// Empty the journal while keeping the state
+    pub fn clear_journal(mut self) -> Self {
+        self.journal.clear();
+        self
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn into_state(self) -> State {
+        self.state
+    }
+}
+
+/// Allow `apply` to be chained on the results of journaled commands
+pub trait JournaledChainable {
+    fn apply<T: Applicable + Debug>(self, command: T) -> CmdResult<JournaledState>;
+}
+
+impl JournaledChainable for CmdResult<JournaledState> {
+    /// See `JournaledState::apply` for an example.
+    fn apply<T: Applicable + Debug>(self, command: T) -> CmdResult<JournaledState> {
+        match self {
+            Ok(journaled) => journaled.apply(command),
+            Err(_) => self,
+        }
+    }
+}
@@ -14,6 +14,12 @@ pub mod command;
 pub mod state;
 /// A set of action helpers and types
 pub mod applicable;
+/// Record the commands applied to a state
+pub mod journal;
+/// Seedable random rolls
+pub mod dice;
+/// Events produced by applying commands
+pub mod event;
 
 
 // State components
@@ -1,6 +1,8 @@
 /// # Character Model
 
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// ## Character > Command Applicables (Cmd)
 /// A simple wrapper for character commands so that they can be composed together with other pipelines.
@@ -8,11 +10,12 @@ use crate::prelude::*;
 
 pub type CharacterId = PubId;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Character {
-    Add(CharacterId, &'static Name),
+    Add(CharacterId, Cow<'static, Name>),
+    AddMany(Vec<(CharacterId, String)>),
     Remove(CharacterId),
-    Rename(CharacterId, &'static Name),
+    Rename(CharacterId, Cow<'static, Name>),
     AssignPlayer(CharacterId, PlayerId),
     RemovePlayer(CharacterId),
     RemovePlayerFromAll(PlayerId)
@@ -22,6 +25,7 @@ impl Applicable for Character {
     fn apply_to(self, state: State) -> CmdResult<State> {
         match self {
             Character::Add(pub_id, name) => cmd::add(state, pub_id, name),
+            Character::AddMany(roster) => cmd::add_many(state, roster),
             Character::Remove(pub_id) => cmd::remove(state, pub_id),
             Character::Rename(pub_id, name) => cmd::rename(state, pub_id, name),
             Character::AssignPlayer(pub_id, player_pub_id) => cmd::assign_player(state, pub_id, player_pub_id),
@@ -45,18 +49,69 @@ pub mod cmd {
     ///
     /// let pub_id = 100;
     /// let state = State::default()
-    ///     .apply(Character::Add(pub_id, "ACharacter"))
+    ///     .apply(Character::Add(pub_id, "ACharacter".into()))
     ///     .unwrap();
     ///
     /// assert!(character::qry::exists(&state,pub_id));
     /// assert_eq!(character::qry::name(&state,pub_id), "ACharacter".to_string());
     /// ```
-    pub fn add(state: State, character_pub_id: PubId, starting_name: &'static Name) -> CmdResult<State> {
+    pub fn add(state: State, character_pub_id: PubId, starting_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
         vec![
             Entity::Add(character_pub_id),
             Entity::Classify(character_pub_id, EntityType::Character),
-            Entity::Name(character_pub_id, starting_name),
         ].apply_to(state)
+            .apply(|state| entity::cmd::name(state, character_pub_id, starting_name))
+    }
+
+    /// COMMAND > Add many characters at once
+    ///
+    /// The roster is validated up front so that either every character is
+    /// added or none are.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::AddMany(vec![
+    ///         (100, "Aria".to_string()),
+    ///         (200, "Brom".to_string()),
+    ///         (300, "Cyd".to_string()),
+    ///     ]))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::name(&state, 100), "Aria".to_string());
+    /// assert_eq!(character::qry::name(&state, 200), "Brom".to_string());
+    /// assert_eq!(character::qry::name(&state, 300), "Cyd".to_string());
+    ///
+    /// // A pub_id that is already taken aborts the whole import
+    /// let imported = state.clone()
+    ///     .apply( |state| character::cmd::add_many(state, vec![
+    ///         (400, "Dax".to_string()),
+    ///         (200, "Brom".to_string()),
+    ///     ]));
+    /// assert!(imported.is_err());
+    ///
+    /// // As does a pub_id repeated within the roster
+    /// let imported = state.clone()
+    ///     .apply( |state| character::cmd::add_many(state, vec![
+    ///         (400, "Dax".to_string()),
+    ///         (400, "Eve".to_string()),
+    ///     ]));
+    /// assert!(imported.is_err());
+    /// ```
+    pub fn add_many(state: State, roster: Vec<(CharacterId, String)>) -> CmdResult<State> {
+        for (index, (character_pub_id, _)) in roster.iter().enumerate() {
+            entity::grd::must_not_exist(&state, *character_pub_id)?;
+            if roster[..index].iter().any(|(pub_id, _)| pub_id == character_pub_id) {
+                return Err(format!("Can not add character {} more than once", character_pub_id));
+            }
+        }
+
+        roster
+            .into_iter()
+            .map(|(character_pub_id, name)| Character::Add(character_pub_id, name.into()))
+            .collect::<Vec<Character>>()
+            .apply_to(state)
     }
 
     /// COMMAND > Assign a character to a player
@@ -66,8 +121,8 @@ pub mod cmd {
     /// let player_pub_id = 100;
     /// let character_pub_id = 200;
     /// let state = State::default()
-    ///     .apply( Character::Add(character_pub_id,"ACharacter"))
-    ///     .apply( Player::Add(player_pub_id,"APlayer") )
+    ///     .apply( Character::Add(character_pub_id,"ACharacter".into()))
+    ///     .apply( Player::Add(player_pub_id,"APlayer".into()) )
     ///     .apply( Character::AssignPlayer(character_pub_id,player_pub_id))
     ///     .unwrap();
     ///
@@ -100,8 +155,8 @@ pub mod cmd {
     /// let player_pub_id = 100;
     /// let character_pub_id = 200;
     /// let state = State::default()
-    ///     .apply( Character::Add(character_pub_id,"ACharacter"))
-    ///     .apply( Player::Add(player_pub_id,"APlayer") )
+    ///     .apply( Character::Add(character_pub_id,"ACharacter".into()))
+    ///     .apply( Player::Add(player_pub_id,"APlayer".into()) )
     ///     .apply( Character::AssignPlayer(character_pub_id,player_pub_id))
     ///     .apply( Character::RemovePlayer(character_pub_id) )
     ///     .unwrap();
@@ -129,10 +184,10 @@ pub mod cmd {
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
-    ///     .apply( Player::Add(100,"APlayer") )
-    ///     .apply( Character::Add(200,"ACharacter"))
+    ///     .apply( Player::Add(100,"APlayer".into()) )
+    ///     .apply( Character::Add(200,"ACharacter".into()))
     ///     .apply( Character::AssignPlayer(200,100))
-    ///     .apply( Character::Add(300,"BCharacter"))
+    ///     .apply( Character::Add(300,"BCharacter".into()))
     ///     .apply( Character::AssignPlayer(300,100))
     ///     .unwrap();
     ///
@@ -160,15 +215,29 @@ pub mod cmd {
 
 
     /// COMMAND > Rename a character
-    pub fn rename(state: State, character_pub_id: PubId, new_name: &'static Name) -> CmdResult<State> {
-        Entity::Name(character_pub_id, new_name).apply_to(state)
+    /// Names can be built at runtime, e.g. from user input.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let runtime_name = format!("{}-{}", "Sir", 100);
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( Character::Rename(100, runtime_name.clone().into()) )
+    ///     .apply( |state| character::cmd::add(state, 200, runtime_name) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::name(&state, 100), "Sir-100".to_string());
+    /// assert_eq!(character::qry::name(&state, 200), "Sir-100".to_string());
+    /// ```
+    pub fn rename(state: State, character_pub_id: PubId, new_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
+        entity::cmd::name(state, character_pub_id, new_name)
     }
 
     /// COMMAND > Remove a character
     ///```
     /// use yourupnext::prelude::*;
     /// let state = State::default()
-    ///    .apply( Character::Add(100,"ACharacter") )
+    ///    .apply( Character::Add(100,"ACharacter".into()) )
     ///    .apply( Character::Remove(100) )
     ///    .unwrap();
     ///
@@ -182,6 +251,77 @@ pub mod cmd {
         let _ = state.character_player.remove_parent(id);
         entity::cmd::remove(state, character_pub_id.clone())
     }
+
+    /// COMMAND > Stash a copy of a character's stats under a named slot, e.g.
+    /// before a polymorph temporarily changes them
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Wizard".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 12) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "str", 8) )
+    ///     .apply( |state| character::cmd::stash_attributes(state, 100, "polymorph") )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 40) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "str", 19) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "bite", 6) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::stat(&state, 100, "hp"), 40);
+    /// assert_eq!(stat::qry::stat(&state, 100, "str"), 19);
+    ///
+    /// let state = state
+    ///     .apply( |state| character::cmd::restore_attributes(state, 100, "polymorph") )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(stat::qry::stat(&state, 100, "hp"), 12);
+    /// assert_eq!(stat::qry::stat(&state, 100, "str"), 8);
+    /// assert_eq!(stat::qry::stats(&state, 100).len(), 2);
+    ///
+    /// // Restoring empties the slot
+    /// assert!( state.clone().apply( |state| character::cmd::restore_attributes(state, 100, "polymorph") ).is_err() );
+    ///
+    /// // Only characters can stash their attributes
+    /// assert!( state.apply( |state| character::cmd::stash_attributes(state, 200, "polymorph") ).is_err() );
+    /// ```
+    pub fn stash_attributes(mut state: State, character_pub_id: PubId, key: &str) -> CmdResult<State> {
+        if !qry::exists(&state, character_pub_id) {
+            return Err("Can not stash the attributes of a non existent character".to_string());
+        }
+
+        let id = qry::id(&state, character_pub_id);
+        let mut stash = state.stat_stash.get_or_default(id);
+        stash.insert(key.to_string(), stat::qry::stats(&state, character_pub_id));
+        state.stat_stash.update(id, stash)?;
+        Ok(state)
+    }
+
+    /// COMMAND > Replace a character's stats with the ones stashed under a named
+    /// slot, emptying the slot
+    /// See `character::cmd::stash_attributes` for tests
+    pub fn restore_attributes(mut state: State, character_pub_id: PubId, key: &str) -> CmdResult<State> {
+        if !qry::exists(&state, character_pub_id) {
+            return Err("Can not restore the attributes of a non existent character".to_string());
+        }
+
+        let id = qry::id(&state, character_pub_id);
+        let mut stash = state.stat_stash.get_or_default(id);
+        let stats = match stash.remove(key) {
+            Some(stats) => stats,
+            None => return Err(format!("Can not restore attributes, nothing is stashed as \"{}\"", key)),
+        };
+
+        match stash.is_empty() {
+            true => state.stat_stash.delete(id)?,
+            false => state.stat_stash.update(id, stash)?,
+        }
+
+        state.stats.retain(|(stat_id, stat_name), _| *stat_id != id || stats.contains_key(stat_name));
+        for (stat_name, value) in stats {
+            state.stats.update((id, stat_name), value)?;
+        }
+        Ok(state)
+    }
 }
 
 
@@ -195,7 +335,7 @@ pub mod qry {
     /// use yourupnext::prelude::*;
     ///
     /// let pub_id: PubId = 123;
-    /// let state = Character::Add(pub_id,"ACharacter")
+    /// let state = Character::Add(pub_id,"ACharacter".into())
     ///     .apply_to_default()
     ///     .unwrap();
     ///
@@ -210,7 +350,7 @@ pub mod qry {
     /// use yourupnext::prelude::*;
     ///
     /// let pub_id: PubId = 100;
-    /// let state = Character::Add(pub_id,"ACharacter")
+    /// let state = Character::Add(pub_id,"ACharacter".into())
     ///     .apply_to_default()
     ///     .unwrap();
     ///
@@ -233,8 +373,8 @@ pub mod qry {
     /// let player_public_id = 100;
     /// let character_public_id = 200;
     /// let state = State::default()
-    ///     .apply( Player::Add(player_public_id,"APlayer") )
-    ///     .apply( Character::Add(character_public_id,"ACharacter") )
+    ///     .apply( Player::Add(player_public_id,"APlayer".into()) )
+    ///     .apply( Character::Add(character_public_id,"ACharacter".into()) )
     ///     .apply( Character::AssignPlayer(character_public_id,player_public_id) )
     ///     .unwrap();
     ///
@@ -253,6 +393,30 @@ pub mod qry {
         entity::qry::pub_id(state, parent_id)
     }
 
+    /// QUERY > Get the Public Id of the scenario a character is captured by
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::scenario(&state, 200), Some(100));
+    /// assert_eq!(character::qry::scenario(&state, 300), None);
+    ///
+    /// // Only characters are looked up
+    /// assert_eq!(character::qry::scenario(&state, 100), None);
+    /// ```
+    pub fn scenario(state: &State, character_pub_id: PubId) -> Option<PubId> {
+        if ! exists(state, character_pub_id) {
+            return None;
+        }
+        scenario::qry::find_entity(state, character_pub_id)
+    }
+
     /// QUERY > Get a characters's `name` as String
     /// See `name` component for tests
     pub fn name(state: &State, player_pub_id: PubId) -> String {
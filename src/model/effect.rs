@@ -0,0 +1,253 @@
+/// # Effect Model
+/// An effect is an entity that couples a source entity with a target entity,
+/// e.g. a spell cast by one character on another. The effect's label is
+/// stored as its name.
+///
+/// ## Duration
+/// Effects last `Forever` unless given a `Duration`. Round based durations
+/// are expired by `seq_play::cmd::next_round` and `WhileSourceExists`
+/// effects are removed along with their source entity.
+///
+/// ## Todo
+/// - Modifiers applied by effects to the values of their targets
+use crate::prelude::*;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Duration {
+    UntilNextRound,
+    ThroughRound(usize),
+    WhileSourceExists,
+    Forever,
+}
+
+/// ## Effect > Command Applicables (Cmd)
+/// A simple wrapper for effect commands so that they can be composed together with other pipelines.
+/// `Cmd` is a facade for `cmd` functions.
+pub type EffectId = PubId;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Effect {
+    Add(EffectId, &'static Name, PubId, PubId),
+    Remove(EffectId),
+    SetDuration(EffectId, Duration),
+}
+
+impl Applicable for Effect {
+    fn apply_to(self, state: State) -> CmdResult<State> {
+        match self {
+            Effect::Add(pub_id, label, source_pub_id, target_pub_id) => {
+                cmd::add(state, pub_id, label, source_pub_id, target_pub_id)
+            }
+            Effect::Remove(pub_id) => cmd::remove(state, pub_id),
+            Effect::SetDuration(pub_id, duration) => cmd::set_duration(state, pub_id, duration),
+        }
+    }
+    fn apply_to_default(self) -> CmdResult<State> {
+        self.apply_to(State::default())
+    }
+}
+
+/// ## Effect > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Add an effect from a source entity to a target entity
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Wizard".into()) )
+    ///     .apply( Character::Add(200, "Fighter".into()) )
+    ///     .apply( Effect::Add(300, "Haste", 100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert!(effect::qry::exists(&state, 300));
+    /// assert_eq!(effect::qry::label(&state, 300), "Haste".to_string());
+    /// assert_eq!(effect::qry::source(&state, 300), Some(100));
+    /// assert_eq!(effect::qry::target(&state, 300), Some(200));
+    /// assert_eq!(effect::qry::effects_on(&state, 200), vec![300]);
+    /// assert_eq!(effect::qry::effects_from(&state, 100), vec![300]);
+    ///
+    /// // The source and target must exist
+    /// assert!(state.apply( Effect::Add(400, "Slow", 100, 500) ).is_err());
+    /// ```
+    pub fn add(
+        mut state: State,
+        effect_pub_id: EffectId,
+        label: &'static Name,
+        source_pub_id: PubId,
+        target_pub_id: PubId,
+    ) -> CmdResult<State> {
+        if !entity::qry::exists(&state, source_pub_id) {
+            return Err("Can not add an effect from a non existent source entity".to_string());
+        }
+
+        if !entity::qry::exists(&state, target_pub_id) {
+            return Err("Can not add an effect to a non existent target entity".to_string());
+        }
+
+        let mut state = vec![
+            Entity::Add(effect_pub_id),
+            Entity::Classify(effect_pub_id, EntityType::Effect),
+            Entity::Name(effect_pub_id, label.into()),
+        ].apply_to(state)?;
+
+        let effect_id = entity::qry::id(&state, effect_pub_id);
+        let source_id = entity::qry::id(&state, source_pub_id);
+        let target_id = entity::qry::id(&state, target_pub_id);
+
+        state.effect_source.set_parent(effect_id, source_id)?;
+        state.effect_target.set_parent(effect_id, target_id)?;
+
+        Ok(state)
+    }
+
+    /// COMMAND > Remove an effect
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Wizard".into()) )
+    ///     .apply( Character::Add(200, "Fighter".into()) )
+    ///     .apply( Effect::Add(300, "Haste", 100, 200) )
+    ///     .apply( Effect::Remove(300) )
+    ///     .unwrap();
+    ///
+    /// assert!(!effect::qry::exists(&state, 300));
+    /// assert_eq!(effect::qry::effects_on(&state, 200), Vec::<PubId>::new());
+    ///
+    /// // Only effects can be removed as effects
+    /// assert!(state.apply( Effect::Remove(100) ).is_err());
+    /// ```
+    pub fn remove(state: State, effect_pub_id: EffectId) -> CmdResult<State> {
+        if !qry::exists(&state, effect_pub_id) {
+            return Err("Can not remove an effect that doesn't exist".to_string());
+        }
+        Entity::Remove(effect_pub_id).apply_to(state)
+    }
+
+    /// COMMAND > Set how long an effect lasts
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Wizard".into()) )
+    ///     .apply( Character::Add(200, "Fighter".into()) )
+    ///     .apply( Effect::Add(300, "Haste", 100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(effect::qry::duration(&state, 300), Duration::Forever);
+    ///
+    /// let state = state
+    ///     .apply( Effect::SetDuration(300, Duration::WhileSourceExists) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(effect::qry::duration(&state, 300), Duration::WhileSourceExists);
+    ///
+    /// // Removing the source removes the effect
+    /// let state = state.apply( Character::Remove(100) ).unwrap();
+    ///
+    /// assert!(!effect::qry::exists(&state, 300));
+    /// assert_eq!(effect::qry::effects_on(&state, 200), Vec::<PubId>::new());
+    /// ```
+    pub fn set_duration(mut state: State, effect_pub_id: EffectId, duration: Duration) -> CmdResult<State> {
+        if !qry::exists(&state, effect_pub_id) {
+            return Err("Can not set the duration of an effect that doesn't exist".to_string());
+        }
+        let effect_id = entity::qry::id(&state, effect_pub_id);
+        state.effect_duration.update(effect_id, duration)?;
+        Ok(state)
+    }
+
+    /// COMMAND > Expire or count down the round based durations of the effects
+    /// on a set of entities as a new round begins
+    /// See `seq_play::cmd::next_round` for tests
+    pub fn expire_round(state: State, target_pub_ids: Vec<PubId>) -> CmdResult<State> {
+        let effects: Vec<EffectId> = target_pub_ids
+            .into_iter()
+            .flat_map(|target_pub_id| qry::effects_on(&state, target_pub_id))
+            .collect();
+
+        state.apply_with(effects, |effect_pub_id| {
+            move |state: State| match qry::duration(&state, effect_pub_id) {
+                Duration::UntilNextRound | Duration::ThroughRound(0..=1) => {
+                    remove(state, effect_pub_id)
+                }
+                Duration::ThroughRound(rounds) => {
+                    set_duration(state, effect_pub_id, Duration::ThroughRound(rounds - 1))
+                }
+                Duration::WhileSourceExists if qry::source(&state, effect_pub_id).is_none() => {
+                    remove(state, effect_pub_id)
+                }
+                _ => Ok(state),
+            }
+        })
+    }
+}
+
+/// ## Effect > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Check if an effect exists
+    /// See `entity_type` component for tests
+    pub fn exists(state: &State, effect_pub_id: EffectId) -> bool {
+        entity_type::qry::is(state, effect_pub_id, EntityType::Effect)
+    }
+
+    /// QUERY > Get an effect's label as String
+    /// See `name` component for tests
+    pub fn label(state: &State, effect_pub_id: EffectId) -> String {
+        name::qry::get(state, effect_pub_id)
+    }
+
+    /// QUERY > Get the Public Id of the entity that is the source of an effect
+    /// See `effect::cmd::add` for tests
+    pub fn source(state: &State, effect_pub_id: EffectId) -> Option<PubId> {
+        if !exists(state, effect_pub_id) {
+            return None;
+        }
+        let effect_id = entity::qry::id(state, effect_pub_id);
+        entity::qry::pub_id(state, state.effect_source.parent(effect_id)?)
+    }
+
+    /// QUERY > Get the Public Id of the entity that is the target of an effect
+    /// See `effect::cmd::add` for tests
+    pub fn target(state: &State, effect_pub_id: EffectId) -> Option<PubId> {
+        if !exists(state, effect_pub_id) {
+            return None;
+        }
+        let effect_id = entity::qry::id(state, effect_pub_id);
+        entity::qry::pub_id(state, state.effect_target.parent(effect_id)?)
+    }
+
+    /// QUERY > Get how long an effect lasts
+    /// See `effect::cmd::set_duration` for tests
+    pub fn duration(state: &State, effect_pub_id: EffectId) -> Duration {
+        let effect_id = entity::qry::id(state, effect_pub_id);
+        state.effect_duration.get_or(effect_id, Duration::Forever)
+    }
+
+    /// QUERY > Get the Public Ids of the effects that only last while their source exists
+    /// See `effect::cmd::set_duration` for tests
+    pub fn dependent_effects(state: &State, source_pub_id: PubId) -> Vec<EffectId> {
+        effects_from(state, source_pub_id)
+            .into_iter()
+            .filter(|effect_pub_id| duration(state, *effect_pub_id) == Duration::WhileSourceExists)
+            .collect()
+    }
+
+    /// QUERY > Get the Public Ids of the effects targeting an entity
+    /// See `effect::cmd::add` for tests
+    pub fn effects_on(state: &State, target_pub_id: PubId) -> Vec<EffectId> {
+        let target_id = entity::qry::id(state, target_pub_id);
+        entity::qry::pub_ids(state, state.effect_target.children(target_id))
+    }
+
+    /// QUERY > Get the Public Ids of the effects originating from an entity
+    /// See `effect::cmd::add` for tests
+    pub fn effects_from(state: &State, source_pub_id: PubId) -> Vec<EffectId> {
+        let source_id = entity::qry::id(state, source_pub_id);
+        entity::qry::pub_ids(state, state.effect_source.children(source_id))
+    }
+}
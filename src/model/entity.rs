@@ -8,6 +8,7 @@
 
 use crate::prelude::*;
 use crate::registry;
+use std::borrow::Cow;
 
 /// ## Entity > Command Applicables (Cmd)
 /// A simple wrapper for entity commands so that they can be composed together with other pipelines.
@@ -15,12 +16,14 @@ use crate::registry;
 
 pub type EntityId = PubId;
 
-#[derive(Debug,Eq,PartialEq)]
+#[derive(Debug,Clone,Eq,PartialEq)]
 pub enum Entity {
     Add(PubId),
+    AddNamedDefault(PubId, EntityType),
     Remove(PubId),
     Classify(PubId, EntityType),
-    Name(PubId, &'static Name),
+    Reclassify(PubId, EntityType),
+    Name(PubId, Cow<'static, Name>),
     Describe(PubId, &'static Description)
 }
 
@@ -28,8 +31,10 @@ impl Applicable for Entity {
     fn apply_to(self, state: State) -> CmdResult<State> {
         match self {
             Entity::Add(pub_id) => cmd::add(state, pub_id),
+            Entity::AddNamedDefault(pub_id, entity_type) => cmd::add_named_default(state, pub_id, entity_type),
             Entity::Remove(pub_id) => cmd::remove(state, pub_id),
             Entity::Classify(pub_id, entity_type) => cmd::classify(state, pub_id, entity_type),
+            Entity::Reclassify(pub_id, entity_type) => cmd::reclassify(state, pub_id, entity_type),
             Entity::Name(pub_id, name) => cmd::name(state, pub_id, name),
             Entity::Describe(pub_id, description) => cmd::describe(state, pub_id, description)
         }
@@ -63,16 +68,42 @@ pub mod cmd {
         registry::register(state, pub_id)
     }
 
-    /// COMMAND > Remove an entity
+    /// COMMAND > Add an entity of a type, named with the type's default name
+    /// ```
+    /// use yourupnext::prelude::*;
+    /// let state = State::default()
+    ///     .apply( Entity::AddNamedDefault(100, EntityType::Character) )
+    ///     .unwrap();
+    ///
+    /// assert!(character::qry::exists(&state, 100));
+    /// assert_eq!(character::qry::name(&state, 100), "New Character".to_string());
+    ///
+    /// // Entities can't be created as missing
+    /// assert!(state.apply( Entity::AddNamedDefault(200, EntityType::Missing) ).is_err());
+    /// ```
+    pub fn add_named_default(state: State, pub_id: PubId, entity_type: EntityType) -> CmdResult<State> {
+        vec![
+            Entity::Add(pub_id),
+            Entity::Classify(pub_id, entity_type.clone()),
+            Entity::Name(pub_id, entity_type.default_name().into()),
+        ].apply_to(state)
+    }
+
+    /// COMMAND > Remove an entity, purging it from all components
     /// ```
     /// use yourupnext::prelude::*;
     /// let state = entity::cmd::add( State::default(), 100).unwrap();
+    /// let state = entity::cmd::name( state, 100, "AName" ).unwrap();
     /// let removed_state = entity::cmd::remove( state, 100).unwrap();
-    /// assert_eq!(entity::qry::id(&removed_state,100), 0)
+    /// assert_eq!(entity::qry::id(&removed_state,100), 0);
+    /// assert!(removed_state.name.is_empty());
     /// ```
     pub fn remove(mut state: State, pub_id: PubId) -> CmdResult<State> {
         let id = qry::id(&state, pub_id);
-        registry::deregister(state, id)
+        let dependent_effects = effect::qry::dependent_effects(&state, pub_id);
+        registry::deregister_pub(state, pub_id)
+            .and_then(|state| state::purge_entity(state, id))
+            .apply_with(dependent_effects, Effect::Remove)
     }
 
     /// COMMAND > Apply a classification (type) to an entity
@@ -87,6 +118,24 @@ pub mod cmd {
         entity_type::cmd::classify(state, entity_pub_id, entity_type)
     }
 
+    /// COMMAND > Intentionally change the classification (type) of an entity
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Entity::Add(100) )
+    ///     .apply( Entity::Classify(100, EntityType::Player) )
+    ///     .unwrap();
+    ///
+    /// assert!( state.clone().apply( Entity::Classify(100, EntityType::Character) ).is_err() );
+    ///
+    /// let state = state.apply( Entity::Reclassify(100, EntityType::Character) ).unwrap();
+    /// assert!( entity::qry::is(&state, 100, EntityType::Character) );
+    /// ```
+    pub fn reclassify(state: State, entity_pub_id: PubId, entity_type: EntityType) -> CmdResult<State> {
+        entity_type::cmd::reclassify(state, entity_pub_id, entity_type)
+    }
+
     /// COMMAND > Rename an entity
     /// ```
     /// use yourupnext::prelude::*;
@@ -94,11 +143,129 @@ pub mod cmd {
     /// let renamed_state = entity::cmd::name( state, 100, "AName" ).unwrap();
     /// assert_eq!(entity::qry::name(&renamed_state,100), "AName".to_string() )
     /// ```
-    pub fn name(state: State, entity_pub_id: PubId, new_name: &'static Name) -> CmdResult<State> {
+    pub fn name(state: State, entity_pub_id: PubId, new_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
         name::cmd::set(state, entity_pub_id, new_name)
     }
 
 
+    /// COMMAND > Clone an entity into a new Public Id
+    ///
+    /// The clone copies the source's type, description, tags and stats. Its
+    /// name is the source's name with the next free copy number (see
+    /// `entity::qry::copy_name`). Turns, scenario membership and other
+    /// relationships are not copied.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "Goblin".into()) )
+    ///     .apply( |state| description::cmd::set(state, 100, "Small and mean") )
+    ///     .apply( |state| tags::cmd::add_tag(state, 100, "monster") )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 7) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( |state| entity::cmd::clone_entity(state, 100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert!( character::qry::exists(&state, 200) );
+    /// assert_eq!( entity::qry::name(&state, 200), "Goblin 1".to_string() );
+    /// assert_eq!( entity::qry::description(&state, 200), "Small and mean".to_string() );
+    /// assert_eq!( tags::qry::tags(&state, 200), vec!["monster".to_string()] );
+    /// assert_eq!( stat::qry::stat(&state, 200, "hp"), 7 );
+    /// assert_eq!( turn_order::qry::sequence(&state, 50), vec![100] );
+    ///
+    /// // The clone's components are independent of the source's
+    /// let state = state
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 200, "hp", -5) )
+    ///     .unwrap();
+    /// assert_eq!( stat::qry::stat(&state, 100, "hp"), 7 );
+    /// assert_eq!( stat::qry::stat(&state, 200, "hp"), 2 );
+    ///
+    /// // The new Public Id must be free and the source must exist
+    /// assert!( state.clone().apply( |state| entity::cmd::clone_entity(state, 100, 200) ).is_err() );
+    /// assert!( state.apply( |state| entity::cmd::clone_entity(state, 999, 300) ).is_err() );
+    /// ```
+    pub fn clone_entity(state: State, source_pub_id: PubId, new_pub_id: PubId) -> CmdResult<State> {
+        grd::must_exist(&state, source_pub_id)?;
+        grd::must_not_exist(&state, new_pub_id)?;
+
+        let new_name = qry::copy_name(&state, &qry::name(&state, source_pub_id));
+        copy_components(state, source_pub_id, new_pub_id, new_name)
+    }
+
+    /// COMMAND > Clone an entity several times
+    ///
+    /// Creates `count` clones with the Public Ids `base_pub_id..base_pub_id + count`,
+    /// each named with the next free copy number. If any of those Public Ids
+    /// is taken no clones are made.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Goblin".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "hp", 7) )
+    ///     .apply( |state| entity::cmd::clone_n(state, 100, 200, 3) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![200, 201, 202].into_iter().map(|pub_id| entity::qry::name(&state, pub_id)).collect::<Vec<String>>(),
+    ///     vec!["Goblin 1".to_string(), "Goblin 2".to_string(), "Goblin 3".to_string()]
+    /// );
+    /// assert_eq!( entity::qry::ids(&state, vec![200, 201, 202]), vec![2, 3, 4] );
+    /// assert_eq!( stat::qry::stat(&state, 202, "hp"), 7 );
+    ///
+    /// // A collision with any target Public Id fails the whole batch
+    /// let result = state.clone()
+    ///     .apply( Character::Add(301, "Orc".into()) )
+    ///     .apply( |state| entity::cmd::clone_n(state, 100, 300, 3) );
+    /// assert!( result.is_err() );
+    ///
+    /// // Public Ids past the largest Public Id are an error
+    /// assert!( state.apply( |state| entity::cmd::clone_n(state, 100, usize::MAX, 2) ).is_err() );
+    /// ```
+    pub fn clone_n(state: State, source_pub_id: PubId, base_pub_id: PubId, count: usize) -> CmdResult<State> {
+        grd::must_exist(&state, source_pub_id)?;
+
+        let end = base_pub_id
+            .checked_add(count)
+            .ok_or("Can not clone an entity past the largest Public Id.")?;
+        for new_pub_id in base_pub_id..end {
+            grd::must_not_exist(&state, new_pub_id)?;
+        }
+
+        state.apply_with((base_pub_id..end).collect::<Vec<PubId>>(), |new_pub_id| {
+            move |state| clone_entity(state, source_pub_id, new_pub_id)
+        })
+    }
+
+    /// COMMAND > Add an entity with a copy of another entity's type, description,
+    /// tags, and stats, named `new_name` when the source has a name
+    /// See `entity::cmd::clone_entity` for tests
+    pub fn copy_components(state: State, source_pub_id: PubId, new_pub_id: PubId, new_name: String) -> CmdResult<State> {
+        let source_id = qry::id(&state, source_pub_id);
+        let entity_type = qry::kind(&state, source_pub_id);
+        let has_name = state.name.is_set(source_id);
+
+        let mut state = add(state, new_pub_id)?
+            .apply_if(entity_type != EntityType::Generic, Entity::Classify(new_pub_id, entity_type))
+            .apply_if(has_name, Entity::Name(new_pub_id, new_name.into()))?;
+
+        let new_id = qry::id(&state, new_pub_id);
+        if let Some(description) = state.description.get(source_id) {
+            state.description.update(new_id, description)?;
+        }
+        if let Some(tags) = state.tags.get(source_id) {
+            state.tags.update(new_id, tags)?;
+        }
+        for (stat_name, value) in stat::qry::stats(&state, source_pub_id) {
+            state.stats.update((new_id, stat_name), value)?;
+        }
+
+        Ok(state)
+    }
+
     /// COMMAND > Describe an entity
     /// ```
     /// use yourupnext::prelude::*;
@@ -110,7 +277,7 @@ pub mod cmd {
     ///
     /// assert_eq!(entity::qry::description(&state,100), "AName".to_string() )
     /// ```
-    pub fn describe(state: State, entity_pub_id: PubId, new_name: &'static Name) -> CmdResult<State> {
+    pub fn describe(state: State, entity_pub_id: PubId, new_name: &Description) -> CmdResult<State> {
         description::cmd::set(state, entity_pub_id, new_name)
     }
 
@@ -134,6 +301,73 @@ pub mod qry {
         id(state, entity_pub_id) != 0
     }
 
+    /// QUERY > Get the first free copy name for a base name
+    ///
+    /// Copies are numbered from 1, skipping any number already used by an
+    /// entity's name.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Goblin".into()) )
+    ///     .apply( Character::Add(200, "Goblin 2".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!( entity::qry::copy_name(&state, "Goblin"), "Goblin 1".to_string() );
+    ///
+    /// let state = state
+    ///     .apply( Character::Add(300, "Goblin 1".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!( entity::qry::copy_name(&state, "Goblin"), "Goblin 3".to_string() );
+    /// ```
+    pub fn copy_name(state: &State, base_name: &Name) -> String {
+        (1..)
+            .map(|copy_number| format!("{} {}", base_name, copy_number))
+            .find(|candidate| !state.name.iter().any(|(_, name)| name == candidate))
+            .unwrap_or_default()
+    }
+
+    /// QUERY > Get the Public Ids of every registered entity, sorted ascending
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(300, "ACharacter".into()) )
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Scenario::Add(200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entity::qry::all(&state), vec![100, 200, 300]);
+    /// assert_eq!(entity::qry::all(&State::default()), Vec::<PubId>::new());
+    /// ```
+    pub fn all(state: &State) -> Vec<PubId> {
+        state.registry.pub_ids()
+    }
+
+    /// QUERY > Get the number of registered entities
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Player::Add(200, "BPlayer".into()) )
+    ///     .apply( Character::Add(300, "ACharacter".into()) )
+    ///     .apply( Entity::Add(400) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entity::qry::count(&state), 4);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Player), 2);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Character), 1);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Generic), 1);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Scenario), 0);
+    /// assert_eq!(entity_type::qry::count_of(&state, EntityType::Missing), 0);
+    /// ```
+    pub fn count(state: &State) -> usize {
+        state.registry.len()
+    }
+
     /// QUERY > Get the Id of an entity or 0 if it doesn't exist
     /// ```
     /// use yourupnext::prelude::*;
@@ -151,35 +385,39 @@ pub mod qry {
     }
 
     /// Query > Get the Pub Ids given a set of IDs
+    /// Ids that aren't registered are skipped, the order of the rest is kept.
     /// ```
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
-    ///         .apply( Character::Add(100, "ACharacter") )
-    ///         .apply( Player::Add(200, "APlayer") )
+    ///         .apply( Character::Add(100, "ACharacter".into()) )
+    ///         .apply( Player::Add(200, "APlayer".into()) )
     ///         .unwrap();
     ///
     /// assert_eq!( entity::qry::pub_ids(&state, vec![1,2]), vec![100,200]  );
+    /// assert_eq!( entity::qry::pub_ids(&state, vec![2,0,9,1]), vec![200,100]  );
+    /// assert_eq!( entity::qry::pub_ids(&state, vec![0,9]), Vec::<PubId>::new()  );
     ///
     /// ```
     pub fn pub_ids(state: &State, ids: Vec<Id> ) -> Vec<PubId> {
         ids.iter()
-            .map(|id| qry::pub_id(state, *id))
-            .filter(|maybe_id|maybe_id.is_some())
-            .map(|some_id|some_id.unwrap())
+            .filter_map(|id| qry::pub_id(state, *id))
             .collect()
     }
 
-    /// Query > Get the Pub Ids given a set of IDs
+    /// Query > Get the IDs given a set of Pub Ids
+    /// Pub Ids that aren't registered are skipped, the order of the rest is kept.
     /// ```
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
-    ///         .apply( Character::Add(100, "ACharacter") )
-    ///         .apply( Player::Add(200, "APlayer") )
+    ///         .apply( Character::Add(100, "ACharacter".into()) )
+    ///         .apply( Player::Add(200, "APlayer".into()) )
     ///         .unwrap();
     ///
     /// assert_eq!( entity::qry::ids(&state, vec![100,200]), vec![1,2]  );
+    /// assert_eq!( entity::qry::ids(&state, vec![200,0,999,100]), vec![2,1]  );
+    /// assert_eq!( entity::qry::ids(&state, vec![999]), Vec::<Id>::new()  );
     ///
     /// ```
     pub fn ids(state: &State, pub_ids: Vec<PubId> ) -> Vec<Id> {
@@ -241,6 +479,83 @@ pub mod qry {
     }
 
 
+    /// QUERY > Find entities whose name contains a substring, ignoring case.
+    /// Matches are sorted by Public Id.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(300, "Goblin Archer".into()) )
+    ///     .apply( Character::Add(100, "Hobgoblin".into()) )
+    ///     .apply( Player::Add(200, "Alice".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entity::qry::search_by_name(&state, "GOBLIN"), vec![100, 300]);
+    /// assert_eq!(entity::qry::search_by_name(&state, "arch"), vec![300]);
+    /// assert_eq!(entity::qry::search_by_name(&state, "Bob"), Vec::<PubId>::new());
+    /// ```
+    pub fn search_by_name(state: &State, substring: &Name) -> Vec<PubId> {
+        let substring = substring.to_lowercase();
+        let ids: Vec<Id> = state.name
+            .iter()
+            .filter(|(_, name)| name.to_lowercase().contains(&substring))
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut pub_ids = pub_ids(state, ids);
+        pub_ids.sort();
+        pub_ids
+    }
+
+    /// QUERY > Resolve an identifier typed by a user to a Public Id
+    ///
+    /// A number is used as a Public Id when an entity has it. Otherwise the
+    /// identifier must match exactly one entity's name, ignoring case and
+    /// surrounding whitespace. Ambiguous or unknown names resolve to `None`.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Gandalf".into()) )
+    ///     .apply( Character::Add(200, "Goblin".into()) )
+    ///     .apply( Character::Add(300, "goblin".into()) )
+    ///     .apply( Player::Add(400, "42".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entity::qry::resolve(&state, "100"), Some(100));
+    /// assert_eq!(entity::qry::resolve(&state, " gandalf "), Some(100));
+    /// assert_eq!(entity::qry::resolve(&state, "Goblin"), None);
+    /// assert_eq!(entity::qry::resolve(&state, "Bilbo"), None);
+    ///
+    /// // Numbers that aren't Public Ids fall back to names
+    /// assert_eq!(entity::qry::resolve(&state, "42"), Some(400));
+    /// assert_eq!(entity::qry::resolve(&state, "999"), None);
+    /// assert_eq!(entity::qry::resolve(&state, " "), None);
+    /// ```
+    pub fn resolve(state: &State, identifier: &str) -> Option<PubId> {
+        let identifier = identifier.trim();
+        if identifier.is_empty() {
+            return None;
+        }
+
+        if let Ok(pub_id) = identifier.parse::<PubId>() {
+            if exists(state, pub_id) {
+                return Some(pub_id);
+            }
+        }
+
+        let identifier = identifier.to_lowercase();
+        let mut matches = all(state)
+            .into_iter()
+            .filter(|pub_id| name(state, *pub_id).to_lowercase() == identifier);
+
+        match (matches.next(), matches.next()) {
+            (Some(pub_id), None) => Some(pub_id),
+            _ => None,
+        }
+    }
+
     /// QUERY > Get the Description of an entity or any empty string
     /// if it doesn't exist
     /// ```
@@ -259,3 +574,38 @@ pub mod qry {
 
 
 }
+
+/// ## Entity > Guard (grd)
+pub mod grd {
+    use super::*;
+
+    /// GUARD > Confirm that an entity exists or error
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = entity::cmd::add( State::default(), 100).unwrap();
+    /// assert!(entity::grd::must_exist(&state, 100).is_ok());
+    /// assert!(entity::grd::must_exist(&state, 200).is_err());
+    /// ```
+    pub fn must_exist(state: &State, pub_id: PubId) -> CmdResult<()> {
+        if !qry::exists(state, pub_id) {
+            return Err(format!("Entity {} must exist, but it was not found", pub_id));
+        }
+        Ok(())
+    }
+
+    /// GUARD > Confirm that an entity does not exist or error
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = entity::cmd::add( State::default(), 100).unwrap();
+    /// assert!(entity::grd::must_not_exist(&state, 100).is_err());
+    /// assert!(entity::grd::must_not_exist(&state, 200).is_ok());
+    /// ```
+    pub fn must_not_exist(state: &State, pub_id: PubId) -> CmdResult<()> {
+        if qry::exists(state, pub_id) {
+            return Err(format!("Entity {} must not exist, but it was found", pub_id));
+        }
+        Ok(())
+    }
+}
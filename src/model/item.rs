@@ -0,0 +1,160 @@
+/// # Item Model
+/// Items are entities that can be owned by a `Character` or a `Player`.
+/// An item has at most one owner at a time.
+use crate::prelude::*;
+
+/// ## Item > Command Applicables (Cmd)
+/// A simple wrapper for item commands so that they can be composed together with other pipelines.
+/// `Cmd` is a facade for `cmd` functions.
+pub type ItemId = PubId;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Item {
+    Add(ItemId, &'static Name),
+    Remove(ItemId),
+    AssignOwner(ItemId, PubId),
+}
+
+impl Applicable for Item {
+    fn apply_to(self, state: State) -> CmdResult<State> {
+        match self {
+            Item::Add(pub_id, name) => cmd::add(state, pub_id, name),
+            Item::Remove(pub_id) => cmd::remove(state, pub_id),
+            Item::AssignOwner(pub_id, owner_pub_id) => cmd::assign_owner(state, pub_id, owner_pub_id),
+        }
+    }
+    fn apply_to_default(self) -> CmdResult<State> {
+        self.apply_to(State::default())
+    }
+}
+
+/// ## Item > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Add an item
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Item::Add(100, "Sword") )
+    ///     .unwrap();
+    ///
+    /// assert!(item::qry::exists(&state, 100));
+    /// assert_eq!(item::qry::name(&state, 100), "Sword".to_string());
+    /// assert_eq!(item::qry::owner(&state, 100), None);
+    /// ```
+    pub fn add(state: State, item_pub_id: ItemId, starting_name: &'static Name) -> CmdResult<State> {
+        vec![
+            Entity::Add(item_pub_id),
+            Entity::Classify(item_pub_id, EntityType::Item),
+            Entity::Name(item_pub_id, starting_name.into()),
+        ].apply_to(state)
+    }
+
+    /// COMMAND > Remove an item
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( Item::Add(200, "Sword") )
+    ///     .apply( Item::AssignOwner(200, 100) )
+    ///     .apply( Item::Remove(200) )
+    ///     .unwrap();
+    ///
+    /// assert!(!item::qry::exists(&state, 200));
+    /// assert_eq!(item::qry::inventory_of(&state, 100), Vec::<PubId>::new());
+    ///
+    /// // Only items can be removed as items
+    /// assert!(state.apply( Item::Remove(100) ).is_err());
+    /// ```
+    pub fn remove(state: State, item_pub_id: ItemId) -> CmdResult<State> {
+        if !qry::exists(&state, item_pub_id) {
+            return Err("Can not remove an item that doesn't exist".to_string());
+        }
+        Entity::Remove(item_pub_id).apply_to(state)
+    }
+
+    /// COMMAND > Assign an item to an owner, replacing any previous owner
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Item::Add(300, "Sword") )
+    ///     .apply( Item::Add(400, "Shield") )
+    ///     .apply( Item::AssignOwner(300, 100) )
+    ///     .apply( Item::AssignOwner(300, 200) )
+    ///     .apply( Item::AssignOwner(400, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(item::qry::owner(&state, 300), Some(200));
+    /// assert_eq!(item::qry::inventory_of(&state, 200), vec![300, 400]);
+    /// assert_eq!(item::qry::inventory_of(&state, 100), Vec::<PubId>::new());
+    ///
+    /// // Owners must be characters or players
+    /// assert!(state.apply( Item::AssignOwner(300, 400) ).is_err());
+    /// ```
+    pub fn assign_owner(mut state: State, item_pub_id: ItemId, owner_pub_id: PubId) -> CmdResult<State> {
+        if !qry::exists(&state, item_pub_id) {
+            return Err("Can not assign an owner to an entity that isn't an item".to_string());
+        }
+
+        if !entity_type::qry::is(&state, owner_pub_id, EntityType::Character)
+            && !entity_type::qry::is(&state, owner_pub_id, EntityType::Player) {
+            return Err("Can not assign an item to an owner that isn't a character or player".to_string());
+        }
+
+        let item_id = entity::qry::id(&state, item_pub_id);
+        let owner_id = entity::qry::id(&state, owner_pub_id);
+
+        if state.item_owner.is_child(item_id) {
+            state.item_owner.remove_parent(item_id)?;
+        }
+
+        state.item_owner.set_parent(item_id, owner_id)?;
+
+        Ok(state)
+    }
+}
+
+/// ## Item > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Check if an item exists
+    /// See `entity_type` component for tests
+    pub fn exists(state: &State, item_pub_id: ItemId) -> bool {
+        entity_type::qry::is(state, item_pub_id, EntityType::Item)
+    }
+
+    /// QUERY > Get an item's `Id`
+    pub fn id(state: &State, item_pub_id: ItemId) -> Id {
+        match exists(state, item_pub_id) {
+            true => entity::qry::id(state, item_pub_id),
+            false => 0
+        }
+    }
+
+    /// QUERY > Get an item's `name` as String
+    /// See `name` component for tests
+    pub fn name(state: &State, item_pub_id: ItemId) -> String {
+        name::qry::get(state, item_pub_id)
+    }
+
+    /// QUERY > Get the Public Id of an item's owner
+    /// See `item::cmd::assign_owner` for tests
+    pub fn owner(state: &State, item_pub_id: ItemId) -> Option<PubId> {
+        let item_id = id(state, item_pub_id);
+        entity::qry::pub_id(state, state.item_owner.parent(item_id)?)
+    }
+
+    /// QUERY > Get the Public Ids of the items owned by an entity
+    /// See `item::cmd::assign_owner` for tests
+    pub fn inventory_of(state: &State, owner_pub_id: PubId) -> Vec<ItemId> {
+        let owner_id = entity::qry::id(state, owner_pub_id);
+        entity::qry::pub_ids(state, state.item_owner.children(owner_id))
+    }
+}
@@ -0,0 +1,124 @@
+/// # Link Model
+/// Links are general purpose one to one relationships between two entities,
+/// grouped by a relation name ("owner", "target", "mount", "rival", etc).
+/// An entity can be linked to one other entity per relation. Linking an
+/// entity that is already linked replaces its previous link.
+use crate::prelude::*;
+
+pub type Relation = str;
+
+/// ## Link > Command Applicables (Cmd)
+/// A simple wrapper for link commands so that they can be composed together with other pipelines.
+/// `Cmd` is a facade for `cmd` functions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Link {
+    Assign(&'static Relation, PubId, PubId),
+    Unlink(&'static Relation, PubId),
+}
+
+impl Applicable for Link {
+    fn apply_to(self, state: State) -> CmdResult<State> {
+        match self {
+            Link::Assign(relation, pub_id, linked_pub_id) => cmd::assign(state, relation, pub_id, linked_pub_id),
+            Link::Unlink(relation, pub_id) => cmd::unlink(state, relation, pub_id),
+        }
+    }
+    fn apply_to_default(self) -> CmdResult<State> {
+        self.apply_to(State::default())
+    }
+}
+
+/// ## Link > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Link two entities under a relation
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Holmes".into()) )
+    ///     .apply( Character::Add(200, "Moriarty".into()) )
+    ///     .apply( |state| link::cmd::assign(state, "rival", 100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(link::qry::linked(&state, "rival", 100), Some(200));
+    /// assert_eq!(link::qry::linked(&state, "rival", 200), Some(100));
+    /// assert_eq!(link::qry::linked(&state, "mount", 100), None);
+    ///
+    /// // Both entities must exist
+    /// assert!(state.apply( Link::Assign("rival", 100, 300) ).is_err());
+    /// ```
+    pub fn assign(mut state: State, relation: &Relation, pub_id: PubId, linked_pub_id: PubId) -> CmdResult<State> {
+        if !entity::qry::exists(&state, pub_id) || !entity::qry::exists(&state, linked_pub_id) {
+            return Err("Can not link entities that don't exist".to_string());
+        }
+
+        if pub_id == linked_pub_id {
+            return Err("Can not link an entity to itself".to_string());
+        }
+
+        let id = entity::qry::id(&state, pub_id);
+        let linked_id = entity::qry::id(&state, linked_pub_id);
+
+        let association = state.links.entry(relation.to_string()).or_default();
+        association.remove(id)?;
+        association.remove(linked_id)?;
+        association.assign(id, linked_id)?;
+
+        Ok(state)
+    }
+
+    /// COMMAND > Remove an entity's link under a relation
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "Holmes".into()) )
+    ///     .apply( Character::Add(200, "Moriarty".into()) )
+    ///     .apply( Link::Assign("rival", 100, 200) )
+    ///     .apply( Link::Unlink("rival", 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(link::qry::linked(&state, "rival", 100), None);
+    /// assert_eq!(link::qry::linked(&state, "rival", 200), None);
+    ///
+    /// // Only linked entities can be unlinked
+    /// assert!(state.apply( Link::Unlink("rival", 100) ).is_err());
+    /// ```
+    pub fn unlink(mut state: State, relation: &Relation, pub_id: PubId) -> CmdResult<State> {
+        if qry::linked(&state, relation, pub_id).is_none() {
+            return Err("Can not unlink an entity that isn't linked".to_string());
+        }
+
+        let id = entity::qry::id(&state, pub_id);
+
+        if let Some(association) = state.links.get_mut(relation) {
+            association.remove(id)?;
+        }
+
+        Ok(state)
+    }
+}
+
+/// ## Link > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Get the Public Id of the entity linked to an entity under a relation
+    /// See `link::cmd::assign` for tests
+    pub fn linked(state: &State, relation: &Relation, pub_id: PubId) -> Option<PubId> {
+        let association = state.links.get(relation)?;
+        let id = entity::qry::id(state, pub_id);
+
+        if association.has_assoc(&id) {
+            return entity::qry::pub_id(state, association.assoc_id_lookup(&id));
+        }
+
+        if association.is_assoc_id(&id) {
+            return entity::qry::pub_id(state, association.id_lookup(&id));
+        }
+
+        None
+    }
+}
@@ -0,0 +1,173 @@
+/// # Location Model
+/// Locations are entities that other entities can be placed in.
+/// An entity is in at most one location at a time.
+use crate::prelude::*;
+
+/// ## Location > Command Applicables (Cmd)
+/// A simple wrapper for location commands so that they can be composed together with other pipelines.
+/// `Cmd` is a facade for `cmd` functions.
+pub type LocationId = PubId;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Location {
+    Add(LocationId, &'static Name),
+    Place(PubId, LocationId),
+    Remove(LocationId),
+}
+
+impl Applicable for Location {
+    fn apply_to(self, state: State) -> CmdResult<State> {
+        match self {
+            Location::Add(pub_id, name) => cmd::add(state, pub_id, name),
+            Location::Place(entity_pub_id, location_pub_id) => cmd::place(state, entity_pub_id, location_pub_id),
+            Location::Remove(pub_id) => cmd::remove(state, pub_id),
+        }
+    }
+    fn apply_to_default(self) -> CmdResult<State> {
+        self.apply_to(State::default())
+    }
+}
+
+/// ## Location > Command (cmd)
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Add a location
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Location::Add(100, "Tavern") )
+    ///     .unwrap();
+    ///
+    /// assert!(location::qry::exists(&state, 100));
+    /// assert_eq!(location::qry::name(&state, 100), "Tavern".to_string());
+    /// ```
+    pub fn add(state: State, location_pub_id: LocationId, starting_name: &'static Name) -> CmdResult<State> {
+        vec![
+            Entity::Add(location_pub_id),
+            Entity::Classify(location_pub_id, EntityType::Location),
+            Entity::Name(location_pub_id, starting_name.into()),
+        ].apply_to(state)
+    }
+
+    /// COMMAND > Remove a location, leaving its occupants unplaced
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( Location::Add(200, "Tavern") )
+    ///     .apply( Location::Place(100, 200) )
+    ///     .apply( Location::Remove(200) )
+    ///     .unwrap();
+    ///
+    /// assert!(!location::qry::exists(&state, 200));
+    /// assert_eq!(location::qry::where_is(&state, 100), None);
+    ///
+    /// // Only locations can be removed as locations
+    /// assert!(state.apply( Location::Remove(100) ).is_err());
+    /// ```
+    pub fn remove(state: State, location_pub_id: LocationId) -> CmdResult<State> {
+        if !qry::exists(&state, location_pub_id) {
+            return Err("Can not remove a location that doesn't exist".to_string());
+        }
+        Entity::Remove(location_pub_id).apply_to(state)
+    }
+
+    /// COMMAND > Place an entity in a location, moving it out of any prior location
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( Item::Add(200, "Sword") )
+    ///     .apply( Location::Add(300, "Tavern") )
+    ///     .apply( Location::Add(400, "Dungeon") )
+    ///     .apply( Location::Place(100, 300) )
+    ///     .apply( Location::Place(200, 300) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(location::qry::where_is(&state, 100), Some(300));
+    /// assert_eq!(location::qry::occupants(&state, 300), vec![100, 200]);
+    ///
+    /// // Moving an entity takes it out of its prior location
+    /// let state = state.apply( Location::Place(100, 400) ).unwrap();
+    ///
+    /// assert_eq!(location::qry::where_is(&state, 100), Some(400));
+    /// assert_eq!(location::qry::occupants(&state, 300), vec![200]);
+    /// assert_eq!(location::qry::occupants(&state, 400), vec![100]);
+    ///
+    /// // Locations can contain locations, but not themselves
+    /// let state = state.apply( Location::Place(400, 300) ).unwrap();
+    /// assert!(state.clone().apply( Location::Place(300, 400) ).is_err());
+    /// assert!(state.clone().apply( Location::Place(300, 300) ).is_err());
+    ///
+    /// // Entities can only be placed in locations that exist
+    /// assert!(state.clone().apply( Location::Place(100, 200) ).is_err());
+    /// assert!(state.apply( Location::Place(999, 300) ).is_err());
+    /// ```
+    pub fn place(mut state: State, entity_pub_id: PubId, location_pub_id: LocationId) -> CmdResult<State> {
+        if !entity::qry::exists(&state, entity_pub_id) {
+            return Err("Can not place an entity that doesn't exist".to_string());
+        }
+
+        if !qry::exists(&state, location_pub_id) {
+            return Err("Can not place an entity in a location that doesn't exist".to_string());
+        }
+
+        let entity_id = entity::qry::id(&state, entity_pub_id);
+        let location_id = entity::qry::id(&state, location_pub_id);
+
+        if state.entity_location.lineage(location_id).contains(&entity_id) {
+            return Err("Can not place a location within itself or one of its contained locations".to_string());
+        }
+
+        if state.entity_location.is_child(entity_id) {
+            state.entity_location.remove_parent(entity_id)?;
+        }
+
+        state.entity_location.set_parent(entity_id, location_id)?;
+
+        Ok(state)
+    }
+}
+
+/// ## Location > Query (qry)
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Check if a location exists
+    /// See `entity_type` component for tests
+    pub fn exists(state: &State, location_pub_id: LocationId) -> bool {
+        entity_type::qry::is(state, location_pub_id, EntityType::Location)
+    }
+
+    /// QUERY > Get a location's `Id`
+    pub fn id(state: &State, location_pub_id: LocationId) -> Id {
+        match exists(state, location_pub_id) {
+            true => entity::qry::id(state, location_pub_id),
+            false => 0
+        }
+    }
+
+    /// QUERY > Get a location's `name` as String
+    /// See `name` component for tests
+    pub fn name(state: &State, location_pub_id: LocationId) -> String {
+        name::qry::get(state, location_pub_id)
+    }
+
+    /// QUERY > Get the Public Ids of the entities in a location
+    /// See `location::cmd::place` for tests
+    pub fn occupants(state: &State, location_pub_id: LocationId) -> Vec<PubId> {
+        let location_id = id(state, location_pub_id);
+        entity::qry::pub_ids(state, state.entity_location.children(location_id))
+    }
+
+    /// QUERY > Get the Public Id of the location an entity is in
+    /// See `location::cmd::place` for tests
+    pub fn where_is(state: &State, entity_pub_id: PubId) -> Option<LocationId> {
+        let entity_id = entity::qry::id(state, entity_pub_id);
+        entity::qry::pub_id(state, state.entity_location.parent(entity_id)?)
+    }
+}
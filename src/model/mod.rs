@@ -3,3 +3,7 @@ pub mod player;
 pub mod character;
 pub mod scenario;
 pub mod seq_play;
+pub mod effect;
+pub mod link;
+pub mod item;
+pub mod location;
@@ -1,6 +1,8 @@
 /// # Player Model
 
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// ## Player > Command Applicables (Cmd)
 /// A simple wrapper for player commands so that they can be composed together with other pipelines.
@@ -8,17 +10,19 @@ use crate::prelude::*;
 
 pub type PlayerId = PubId;
 
-#[derive(Debug,Eq,PartialEq)]
+#[derive(Debug,Clone,Eq,PartialEq,Serialize,Deserialize)]
 pub enum Player {
-    Add(PlayerId, &'static Name),
+    Add(PlayerId, Cow<'static, Name>),
+    AddUnique(PlayerId, Cow<'static, Name>),
     Remove(PlayerId),
-    Rename(PlayerId, &'static Name)
+    Rename(PlayerId, Cow<'static, Name>)
 }
 
 impl Applicable for Player {
     fn apply_to(self, state: State) -> CmdResult<State> {
         match self {
             Player::Add(player_id, starting_name) => cmd::add(state, player_id, starting_name),
+            Player::AddUnique(player_id, starting_name) => cmd::add_unique(state, player_id, starting_name),
             Player::Remove(player_id) => cmd::remove(state, player_id),
             Player::Rename(player_id, new_name) => cmd::rename(state, player_id, new_name)
         }
@@ -38,7 +42,7 @@ pub mod cmd {
     /// use yourupnext::prelude::*;
     ///
     /// let player_id: PlayerId = 100;
-    /// let state = Player::Add(player_id,"APlayer")
+    /// let state = Player::Add(player_id,"APlayer".into())
     ///     .apply_to_default()
     ///     .unwrap();
     ///
@@ -46,12 +50,36 @@ pub mod cmd {
     /// assert_eq!(player::qry::name(&state,player_id), "APlayer".to_string());
     ///
     /// ```
-    pub fn add(state: State, player_id: PlayerId, starting_name: &'static Name) -> CmdResult<State> {
+    pub fn add(state: State, player_id: PlayerId, starting_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
         vec![
             Entity::Add(player_id),
             Entity::Classify(player_id, EntityType::Player),
-            Entity::Name(player_id, starting_name),
         ].apply_to(state)
+            .apply(|state| entity::cmd::name(state, player_id, starting_name))
+    }
+
+    /// COMMAND > Add a player, rejecting names already taken by another player
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::AddUnique(100, "Bob".into()) )
+    ///     .apply( Player::AddUnique(200, "Alice".into()) )
+    ///     .unwrap();
+    ///
+    /// assert!(player::qry::exists(&state, 200));
+    /// assert!(state.clone().apply( Player::AddUnique(300, "Bob".into()) ).is_err());
+    /// assert!(state.clone().apply( Player::AddUnique(300, "bob".into()) ).is_err());
+    ///
+    /// // Plain adds don't check for duplicates
+    /// assert!(state.apply( Player::Add(300, "bob".into()) ).is_ok());
+    /// ```
+    pub fn add_unique(state: State, player_id: PlayerId, starting_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
+        let starting_name = starting_name.into();
+        if qry::name_taken(&state, &starting_name) {
+            return Err(format!("A player named \"{}\" already exists", starting_name.trim()));
+        }
+        add(state, player_id, starting_name)
     }
 
     /// COMMAND > Remove a player
@@ -62,8 +90,8 @@ pub mod cmd {
 
     /// COMMAND > Rename a player
     /// See Entity::Name for tests
-    pub fn rename(state: State, player_id: PlayerId, new_name: &'static Name) -> CmdResult<State> {
-        Entity::Name(player_id, new_name).apply_to(state)
+    pub fn rename(state: State, player_id: PlayerId, new_name: impl Into<Cow<'static, Name>>) -> CmdResult<State> {
+        entity::cmd::name(state, player_id, new_name)
     }
 
 }
@@ -84,7 +112,7 @@ pub mod qry {
     /// use yourupnext::prelude::*;
     ///
     /// let player_id: PlayerId = 100;
-    /// let state = Player::Add(player_id,"APlayer")
+    /// let state = Player::Add(player_id,"APlayer".into())
     ///     .apply_to_default()
     ///     .unwrap();
     ///
@@ -106,4 +134,129 @@ pub mod qry {
     pub fn name(state: &State, player_id: PlayerId) -> String {
         name::qry::get(state, player_id)
     }
-}
\ No newline at end of file
+
+    /// QUERY > Get the Public Ids of the characters assigned to a player
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100,"APlayer".into()) )
+    ///     .apply( Character::Add(200,"ACharacter".into()))
+    ///     .apply( Character::AssignPlayer(200,100))
+    ///     .apply( Character::Add(300,"BCharacter".into()))
+    ///     .apply( Character::AssignPlayer(300,100))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(player::qry::characters_of(&state, 100), vec![200,300]);
+    /// assert_eq!(player::qry::characters_of(&state, 200), Vec::<PubId>::new());
+    /// ```
+    pub fn characters_of(state: &State, player_id: PlayerId) -> Vec<PubId> {
+        if !exists(state, player_id) {
+            return Vec::with_capacity(0);
+        }
+        let id = entity::qry::id(state, player_id);
+        entity::qry::pub_ids(state, state.character_player.children(id))
+    }
+
+    /// QUERY > Get the (scenario, character) Public Id pairs for each of a player's
+    /// characters that currently holds an `Active` turn in any scenario
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100,"APlayer".into()) )
+    ///     .apply( Scenario::Add(200) )
+    ///     .apply( Scenario::Add(300) )
+    ///     .apply( Character::Add(400,"ACharacter".into()))
+    ///     .apply( Character::AssignPlayer(400,100))
+    ///     .apply( Character::Add(500,"BCharacter".into()))
+    ///     .apply( Character::AssignPlayer(500,100))
+    ///     .apply( SeqPlay::AddTurn(200,400) )
+    ///     .apply( SeqPlay::AddTurn(300,500) )
+    ///     .apply( SeqPlay::Enable(200) )
+    ///     .apply( SeqPlay::Enable(300) )
+    ///     .apply(|state| turn_state::cmd::set(state, 500, TurnStatus::Active))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(player::qry::active_characters(&state, 100), vec![(300,500)]);
+    /// ```
+    pub fn active_characters(state: &State, player_id: PlayerId) -> Vec<(PubId, PubId)> {
+        characters_of(state, player_id)
+            .into_iter()
+            .filter(|character_pub_id| turn_state::qry::get(state, *character_pub_id) == TurnStatus::Active)
+            .filter_map(|character_pub_id| {
+                scenario::qry::find_entity(state, character_pub_id)
+                    .map(|scenario_pub_id| (scenario_pub_id, character_pub_id))
+            })
+            .collect()
+    }
+
+    /// QUERY > Get the number of characters a player has
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Player::Add(150, "BPlayer".into()) )
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( Character::AssignPlayer(200, 100) )
+    ///     .apply( Character::AssignPlayer(300, 100) )
+    ///     .apply( SeqPlay::AddTurns(50, vec![200, 300]) )
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(player::qry::character_count(&state, 100), 2);
+    /// assert_eq!(player::qry::character_count(&state, 150), 0);
+    /// assert!(!player::qry::has_active_turn(&state, 100));
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    ///
+    /// assert!(player::qry::has_active_turn(&state, 100));
+    /// assert!(!player::qry::has_active_turn(&state, 150));
+    /// ```
+    pub fn character_count(state: &State, player_id: PlayerId) -> usize {
+        characters_of(state, player_id).len()
+    }
+
+    /// QUERY > Check if any of a player's characters has an `Active` turn
+    /// See `player::qry::character_count` for tests
+    pub fn has_active_turn(state: &State, player_id: PlayerId) -> bool {
+        characters_of(state, player_id)
+            .into_iter()
+            .any(|character_id| turn_state::qry::get(state, character_id) == TurnStatus::Active)
+    }
+
+    /// QUERY > Find a player by name, ignoring case and surrounding whitespace
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "Bob".into()) )
+    ///     .apply( Character::Add(200, "Carl".into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(player::qry::find_by_name(&state, "Bob"), Some(100));
+    /// assert_eq!(player::qry::find_by_name(&state, "bOB "), Some(100));
+    /// assert_eq!(player::qry::find_by_name(&state, "Carl"), None);
+    ///
+    /// assert!(player::qry::name_taken(&state, "Bob"));
+    /// assert!(player::qry::name_taken(&state, "bob"));
+    /// assert!(!player::qry::name_taken(&state, "Alice"));
+    /// ```
+    pub fn find_by_name(state: &State, player_name: &Name) -> Option<PlayerId> {
+        let player_name = player_name.trim().to_lowercase();
+        state.registry
+            .pub_ids()
+            .into_iter()
+            .filter(|pub_id| exists(state, *pub_id))
+            .find(|pub_id| name(state, *pub_id).to_lowercase() == player_name)
+    }
+
+    /// QUERY > Check if a player already has a name, ignoring case
+    /// See `player::qry::find_by_name` for tests
+    pub fn name_taken(state: &State, player_name: &Name) -> bool {
+        find_by_name(state, player_name).is_some()
+    }
+}
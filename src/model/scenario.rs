@@ -28,21 +28,31 @@
 /// - Create nice API for nesting scenarios
 ///
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 pub type ScenarioId = PubId;
 
-#[derive(Debug, Eq, PartialEq)]
+/// The play mode of a scenario, derived from the scenario's `TurnStatus`.
+/// A scenario in sequenced play has an `Active` turn state, otherwise it is in free play.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PlayMode {
+    Free,
+    Sequenced,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Scenario {
     Add(PubId),
     Remove(PubId),
-    Rename(PubId, &'static Name),
-    Describe(PubId, &'static Name),
+    Rename(PubId, Cow<'static, Name>),
+    Describe(PubId, Cow<'static, Description>),
 
     CaptureEntity(PubId, PubId),
     ReleaseEntity(PubId),
     ReleaseAllEntities(PubId),
 
-    // Nest?
+    Nest(PubId, PubId),
 }
 
 impl Applicable for Scenario {
@@ -51,7 +61,7 @@ impl Applicable for Scenario {
             Scenario::Add(pub_id) => cmd::add(state, pub_id),
             Scenario::Remove(pub_id) => cmd::remove(state, pub_id),
             Scenario::Rename(pub_id, name) => cmd::rename(state, pub_id, name),
-            Scenario::Describe(pub_id, description) => cmd::describe(state, pub_id, description),
+            Scenario::Describe(pub_id, description) => cmd::describe(state, pub_id, &description),
 
             Scenario::CaptureEntity(pub_id, entity_pub_id) => {
                 cmd::assign_entity(state, pub_id, entity_pub_id)
@@ -61,6 +71,8 @@ impl Applicable for Scenario {
             }
             Scenario::ReleaseAllEntities(pub_id) => cmd::release_all_entities(state, pub_id),
 
+            Scenario::Nest(pub_id, parent_pub_id) => cmd::nest(state, pub_id, parent_pub_id),
+
 
             // Scenario::FreePlay(scenario_pub_id) => cmd::free_play(state, scenario_pub_id),
             // Scenario::SeqPlay(scenario_pub_id) => cmd::seq_play(state, scenario_pub_id),
@@ -75,6 +87,95 @@ impl Applicable for Scenario {
     }
 }
 
+/// ## Scenario > Builder
+/// Collects the steps of a multi-step scenario setup and applies them as
+/// one `Applicable` sequence. Characters added with the builder are captured
+/// by the scenario, and turns are added to the scenario's sequence.
+///
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let state = scenario::Builder::default()
+///     .add(100)
+///     .with_character(200, "Alpha")
+///     .with_character(300, "Bravo")
+///     .with_character(400, "Charlie")
+///     .with_turn(200)
+///     .with_turn(300)
+///     .with_turn(400)
+///     .build(State::default())
+///     .unwrap();
+///
+/// assert!(scenario::qry::exists(&state, 100));
+/// assert_eq!(scenario::qry::find_entity(&state, 300), Some(100));
+/// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200, 300, 400]);
+///
+/// // Steps that target the scenario require it to be added first
+/// assert!(scenario::Builder::default()
+///     .with_character(200, "Alpha")
+///     .build(State::default())
+///     .is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct Builder {
+    scenario: Option<ScenarioId>,
+    steps: Vec<BuilderStep>,
+}
+
+#[derive(Debug)]
+enum BuilderStep {
+    Add(ScenarioId),
+    Character(PubId, &'static Name),
+    Turn(PubId),
+}
+
+impl Builder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, scenario_pub_id: ScenarioId) -> Self {
+        self.scenario = Some(scenario_pub_id);
+        self.steps.push(BuilderStep::Add(scenario_pub_id));
+        self
+    }
+
+    pub fn with_character(mut self, character_pub_id: PubId, name: &'static Name) -> Self {
+        self.steps.push(BuilderStep::Character(character_pub_id, name));
+        self
+    }
+
+    pub fn with_turn(mut self, entity_pub_id: PubId) -> Self {
+        self.steps.push(BuilderStep::Turn(entity_pub_id));
+        self
+    }
+
+    pub fn build(self, state: State) -> CmdResult<State> {
+        let scenario_pub_id = self.scenario
+            .ok_or("Can not build a scenario without adding one".to_string())?;
+
+        self.steps
+            .into_iter()
+            .map(|step| step.into_cmd(scenario_pub_id))
+            .collect::<Vec<_>>()
+            .apply_to(state)
+    }
+}
+
+impl BuilderStep {
+    fn into_cmd(self, scenario_pub_id: ScenarioId) -> Vec<Box<dyn FnOnce(State) -> CmdResult<State>>> {
+        match self {
+            BuilderStep::Add(pub_id) => vec![
+                Box::new(move |state| Scenario::Add(pub_id).apply_to(state)),
+            ],
+            BuilderStep::Character(pub_id, name) => vec![
+                Box::new(move |state| Character::Add(pub_id, name.into()).apply_to(state)),
+                Box::new(move |state| Scenario::CaptureEntity(scenario_pub_id, pub_id).apply_to(state)),
+            ],
+            BuilderStep::Turn(pub_id) => vec![
+                Box::new(move |state| SeqPlay::AddTurn(scenario_pub_id, pub_id).apply_to(state)),
+            ],
+        }
+    }
+}
+
 pub mod cmd {
     use super::*;
 
@@ -109,9 +210,9 @@ pub mod cmd {
     pub fn rename(
         state: State,
         scenario_pub_id: PubId,
-        new_name: &'static Name,
+        new_name: impl Into<Cow<'static, Name>>,
     ) -> CmdResult<State> {
-        Entity::Name(scenario_pub_id, new_name).apply_to(state)
+        entity::cmd::name(state, scenario_pub_id, new_name)
     }
 
     /// COMMAND > Describe the scenario
@@ -119,9 +220,9 @@ pub mod cmd {
     pub fn describe(
         state: State,
         scenario_pub_id: PubId,
-        desc: &'static Description,
+        desc: &Description,
     ) -> CmdResult<State> {
-        Entity::Describe(scenario_pub_id, desc).apply_to(state)
+        entity::cmd::describe(state, scenario_pub_id, desc)
     }
 
     /// COMMAND > Assign a character to a scenario
@@ -133,7 +234,7 @@ pub mod cmd {
     ///
     /// let state = State::default()
     ///     .apply( Scenario::Add(scenario_pub_id) )
-    ///     .apply( Character::Add(character_pub_id,"ACharacter"))
+    ///     .apply( Character::Add(character_pub_id,"ACharacter".into()))
     ///     .apply( Scenario::CaptureEntity(scenario_pub_id,character_pub_id))
     ///     .unwrap();
     ///
@@ -175,7 +276,7 @@ pub mod cmd {
     ///
     /// let state = State::default()
     ///     .apply( Scenario::Add(scenario_pub_id) )
-    ///     .apply( Character::Add(character_pub_id, "ACharacter") )
+    ///     .apply( Character::Add(character_pub_id, "ACharacter".into()) )
     ///     .apply( Scenario::CaptureEntity(scenario_pub_id, character_pub_id) )
     ///     .unwrap();
     /// assert_eq!(scenario::qry::find_entity(&state,character_pub_id), Some(100));
@@ -207,6 +308,154 @@ pub mod cmd {
     pub fn release_all_entities(mut state: State, scenario_pub_id: PubId) -> CmdResult<State> {
         Ok(state)
     }
+
+    /// COMMAND > Create a new scenario from a template scenario
+    ///
+    /// The template's name and description are copied to `new_scenario_pub_id`.
+    /// Each captured entity is copied (see `entity::cmd::copy_components`) with
+    /// its Public Id offset by `id_offset` and captured by the new scenario.
+    /// The template's turn order is translated to the copies, and the new
+    /// turns start out the way granted turns do. Nested scenarios aren't copied.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Scenario::Rename(100, "Goblin Ambush".into()) )
+    ///     .apply( Character::Add(200, "Goblin".into()) )
+    ///     .apply( Character::Add(300, "Shaman".into()) )
+    ///     .apply( |state| stat::cmd::set_stat(state, 200, "hp", 7) )
+    ///     .apply( SeqPlay::AddTurn(100, 300) )
+    ///     .apply( SeqPlay::AddTurn(100, 200) )
+    ///     .apply( |state| scenario::cmd::instantiate(state, 100, 1000, 1000) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(scenario::qry::name(&state, 1000), "Goblin Ambush".to_string());
+    /// assert_eq!(scenario::qry::members(&state, 1000), vec![1300, 1200]);
+    /// assert_eq!(turn_order::qry::sequence(&state, 1000), vec![1300, 1200]);
+    /// assert_eq!(entity::qry::name(&state, 1200), "Goblin".to_string());
+    /// assert_eq!(entity_type::qry::get(&state, 1300), EntityType::Character);
+    /// assert_eq!(turn_state::qry::get(&state, 1200), TurnStatus::Free);
+    ///
+    /// // The instance is independent of its template
+    /// let state = state
+    ///     .apply( Character::Rename(1200, "Hobgoblin".into()) )
+    ///     .apply( |state| stat::cmd::adjust_stat(state, 1200, "hp", -3) )
+    ///     .apply( SeqPlay::RemoveTurn(1300) )
+    ///     .apply( SeqPlay::Enable(1000) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(entity::qry::name(&state, 200), "Goblin".to_string());
+    /// assert_eq!(stat::qry::stat(&state, 200, "hp"), 7);
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![300, 200]);
+    /// assert_eq!(scenario::qry::mode(&state, 100), PlayMode::Free);
+    /// assert_eq!(turn_order::qry::sequence(&state, 1000), vec![1200]);
+    ///
+    /// // Every new Public Id must be free and in range
+    /// assert!( state.clone().apply( |state| scenario::cmd::instantiate(state, 100, 2000, 100) ).is_err() );
+    /// assert!( state.clone().apply( |state| scenario::cmd::instantiate(state, 100, 2000, PubId::MAX) ).is_err() );
+    /// assert!( state.apply( |state| scenario::cmd::instantiate(state, 999, 2000, 2000) ).is_err() );
+    /// ```
+    pub fn instantiate(
+        state: State,
+        template_pub_id: PubId,
+        new_scenario_pub_id: PubId,
+        id_offset: PubId,
+    ) -> CmdResult<State> {
+        if !qry::exists(&state, template_pub_id) {
+            return Err("Can not instantiate a scenario from a template that isn't a scenario.".to_string());
+        }
+
+        let members: Vec<PubId> = qry::members(&state, template_pub_id)
+            .into_iter()
+            .filter(|member| !qry::exists(&state, *member))
+            .collect();
+
+        entity::grd::must_not_exist(&state, new_scenario_pub_id)?;
+        let mut copies: Vec<(PubId, PubId)> = Vec::with_capacity(members.len());
+        for member in members {
+            let copy_pub_id = member
+                .checked_add(id_offset)
+                .ok_or("Can not instantiate a scenario when an offset Public Id is out of range.")?;
+            entity::grd::must_not_exist(&state, copy_pub_id)?;
+            copies.push((member, copy_pub_id));
+        }
+
+        let turns: Vec<PubId> = turn_order::qry::sequence(&state, template_pub_id)
+            .into_iter()
+            .filter_map(|turn| copies.iter().find(|(member, _)| *member == turn).map(|(_, copy)| *copy))
+            .collect();
+
+        let name = qry::name(&state, template_pub_id);
+        let description = qry::description(&state, template_pub_id);
+
+        let state = add(state, new_scenario_pub_id)?
+            .apply_if(!name.is_empty(), |state| rename(state, new_scenario_pub_id, name))
+            .apply_if(!description.is_empty(), |state| describe(state, new_scenario_pub_id, &description))
+            .apply_with(copies, |(member, copy_pub_id)| {
+                move |state: State| {
+                    let name = entity::qry::name(&state, member);
+                    entity::cmd::copy_components(state, member, copy_pub_id, name)
+                        .apply(|state| assign_entity(state, new_scenario_pub_id, copy_pub_id))
+                }
+            })?;
+
+        let turn_state = seq_play::qry::new_turn_init_state(&state, new_scenario_pub_id);
+
+        state
+            .apply(|state| turn_order::cmd::set(state, new_scenario_pub_id, turns.clone()))
+            .apply_with(turns, |turn| {
+                move |state| turn_state::cmd::set_in(state, new_scenario_pub_id, turn, turn_state)
+            })
+    }
+
+    /// COMMAND > Nest a scenario within a parent scenario
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Scenario::Add(200) )
+    ///     .apply( Scenario::Nest(200, 100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(scenario::qry::find_entity(&state, 200), Some(100));
+    ///
+    /// // A scenario can not be nested within itself or its own descendants
+    /// assert!( state.clone().apply( Scenario::Nest(100, 100) ).is_err() );
+    /// assert!( state.clone().apply( Scenario::Nest(100, 200) ).is_err() );
+    /// ```
+    pub fn nest(
+        mut state: State,
+        scenario_pub_id: PubId,
+        parent_scenario_pub_id: PubId,
+    ) -> CmdResult<State> {
+        if !qry::exists(&state, scenario_pub_id) {
+            return Err("Can not nest a scenario when the subject scenario isn't a scenario.".to_string());
+        }
+
+        if !qry::exists(&state, parent_scenario_pub_id) {
+            return Err("Can not nest a scenario when the target parent scenario isn't a scenario.".to_string());
+        }
+
+        let scenario_id = entity::qry::id(&state, scenario_pub_id);
+        let parent_scenario_id = entity::qry::id(&state, parent_scenario_pub_id);
+
+        if state.scenario_entity.lineage(parent_scenario_id).contains(&scenario_id) {
+            return Err("Can not nest a scenario within itself or one of its nested scenarios.".to_string());
+        }
+
+        if state.scenario_entity.is_child(scenario_id) {
+            state.scenario_entity.remove_parent(scenario_id)?;
+        }
+
+        state
+            .scenario_entity
+            .set_parent(scenario_id, parent_scenario_id)?;
+
+        Ok(state)
+    }
 }
 
 pub mod qry {
@@ -259,5 +508,96 @@ pub mod qry {
         entity::qry::pub_id(state, scenario_id)
     }
 
+    /// QUERY > Get the Public Ids of the entities that are members of (captured by) a scenario
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .apply( Scenario::CaptureEntity(100, 300) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(scenario::qry::members(&state, 100), vec![200, 300]);
+    /// assert_eq!(scenario::qry::members(&state, 200), Vec::<PubId>::new());
+    /// ```
+    pub fn members(state: &State, scenario_pub_id: PubId) -> Vec<PubId> {
+        if !exists(state, scenario_pub_id) {
+            return Vec::with_capacity(0);
+        }
+        let scenario_id = entity::qry::id(state, scenario_pub_id);
+        entity::qry::pub_ids(state, state.scenario_entity.children(scenario_id))
+    }
+
+    /// QUERY > Find a scenario by a path of names, starting from a root scenario
+    /// and descending through its nested scenarios
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Scenario::Rename(100, "Campaign".into()) )
+    ///     .apply( Scenario::Add(200) )
+    ///     .apply( Scenario::Rename(200, "Dungeon".into()) )
+    ///     .apply( Scenario::Nest(200, 100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(scenario::qry::find_by_path(&state, &["Campaign"]), Some(100));
+    /// assert_eq!(scenario::qry::find_by_path(&state, &["Campaign", "Dungeon"]), Some(200));
+    /// assert_eq!(scenario::qry::find_by_path(&state, &["Dungeon"]), None);
+    /// assert_eq!(scenario::qry::find_by_path(&state, &[]), None);
+    /// ```
+    pub fn find_by_path(state: &State, scenario_path: &[&str]) -> Option<PubId> {
+        let mut candidates: Vec<PubId> = state.registry
+            .pub_ids()
+            .into_iter()
+            .filter(|pub_id| exists(state, *pub_id) && find_entity(state, *pub_id).is_none())
+            .collect();
+
+        let mut found = None;
+
+        for scenario_name in scenario_path {
+            let scenario_pub_id = candidates
+                .into_iter()
+                .find(|pub_id| name(state, *pub_id) == *scenario_name)?;
+
+            let scenario_id = entity::qry::id(state, scenario_pub_id);
+            candidates = entity::qry::pub_ids(state, state.scenario_entity.children(scenario_id))
+                .into_iter()
+                .filter(|pub_id| exists(state, *pub_id))
+                .collect();
+            candidates.sort();
+
+            found = Some(scenario_pub_id);
+        }
 
+        found
+    }
+
+    /// QUERY > Get the play mode of a scenario
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( SeqPlay::AddTurn(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(scenario::qry::mode(&state, 100), PlayMode::Free);
+    ///
+    /// let state = state.apply( SeqPlay::Enable(100) ).unwrap();
+    /// assert_eq!(scenario::qry::mode(&state, 100), PlayMode::Sequenced);
+    ///
+    /// let state = state.apply( SeqPlay::Disable(100) ).unwrap();
+    /// assert_eq!(scenario::qry::mode(&state, 100), PlayMode::Free);
+    /// ```
+    pub fn mode(state: &State, scenario_pub_id: ScenarioId) -> PlayMode {
+        match turn_state::qry::get(state, scenario_pub_id) {
+            TurnStatus::Active => PlayMode::Sequenced,
+            _ => PlayMode::Free,
+        }
+    }
 }
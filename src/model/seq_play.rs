@@ -6,13 +6,22 @@
 ///
 /// TODO ... Consider renaming turn play
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SeqPlay {
     AddTurn(ScenarioId, EntityId),
+    AddTurns(ScenarioId, Vec<EntityId>),
+    GrantTurn(ScenarioId, EntityId),
     RemoveTurn(EntityId),
     Enable(ScenarioId),
     Disable(ScenarioId),
+    NextTurn(ScenarioId),
+    NextRound(ScenarioId),
+    RollInitiative(ScenarioId, EntityId, Initiative),
+    RollAllInitiative(ScenarioId, u8, u64, Option<String>),
+    Interrupt(EntityId),
+    Resume(EntityId),
 }
 
 impl Applicable for SeqPlay {
@@ -21,9 +30,25 @@ impl Applicable for SeqPlay {
             SeqPlay::AddTurn(scenario_id, entity_id) => {
                 cmd::add_turn(state, scenario_id, entity_id)
             }
+            SeqPlay::AddTurns(scenario_id, entity_ids) => {
+                cmd::add_turns(state, scenario_id, entity_ids)
+            }
+            SeqPlay::GrantTurn(scenario_id, entity_id) => {
+                cmd::grant_turn(state, scenario_id, entity_id)
+            }
             SeqPlay::RemoveTurn(entity_id) => cmd::remove_turn(state, entity_id),
             SeqPlay::Enable(scenario_id) => cmd::enable(state, scenario_id),
             SeqPlay::Disable(scenario_id) => cmd::disable(state, scenario_id),
+            SeqPlay::NextTurn(scenario_id) => cmd::next_turn(state, scenario_id),
+            SeqPlay::NextRound(scenario_id) => cmd::next_round(state, scenario_id),
+            SeqPlay::RollInitiative(scenario_id, entity_id, rolled) => {
+                cmd::roll_initiative(state, scenario_id, entity_id, rolled)
+            }
+            SeqPlay::RollAllInitiative(scenario_id, die, seed, modifier_stat) => {
+                cmd::roll_all_initiative(state, scenario_id, die, seed, modifier_stat.as_deref())
+            }
+            SeqPlay::Interrupt(entity_id) => cmd::interrupt(state, entity_id),
+            SeqPlay::Resume(entity_id) => cmd::resume(state, entity_id),
         }
     }
     fn apply_to_default(self) -> CmdResult<State> {
@@ -31,6 +56,17 @@ impl Applicable for SeqPlay {
     }
 }
 
+/// A summary of a turn in a scenario, used by frontends to render a tracker.
+/// `position` is the 1-based position of the turn in the scenario's turn order.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct TurnInfo {
+    pub pub_id: PubId,
+    pub name: String,
+    pub status: TurnStatus,
+    pub turn_count: TurnCount,
+    pub position: usize,
+}
+
 /// ## Sequenced Play Commands
 
 pub mod cmd {
@@ -38,6 +74,7 @@ pub mod cmd {
 
     /// COMMAND > Add a turn to sequenced play
     ///
+    /// The entity is captured by the scenario if it isn't already a member.
     /// The new turn will receive a starting TurnState based on the scenario's TurnState.
     ///
     /// ```
@@ -45,11 +82,32 @@ pub mod cmd {
     ///
     /// let state = State::default()
     ///     .apply( Scenario::Add(100) )
-    ///     .apply( Character::Add(200, "ACharacter"))
+    ///     .apply( Character::Add(200, "ACharacter".into()))
     ///     .apply(|state|seq_play::cmd::add_turn(state, 100, 200));
     ///
     /// assert!(state.is_ok());
     /// assert_eq!(turn_state::qry::get(&state.unwrap(), 200), TurnStatus::Free );
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Scenario::Add(300) )
+    ///     .apply( Character::Add(200, "ACharacter".into()))
+    ///     .apply( Character::Add(400, "BCharacter".into()))
+    ///     .apply( Scenario::CaptureEntity(300, 400) )
+    ///     .unwrap();
+    ///
+    /// // Scenarios can't take turns, not even in other scenarios
+    /// assert!(state.clone().apply(|state|seq_play::cmd::add_turn(state, 100, 300)).is_err());
+    ///
+    /// // Entities captured by another scenario can't take a turn here
+    /// assert!(state.clone().apply(|state|seq_play::cmd::add_turn(state, 100, 400)).is_err());
+    ///
+    /// // Members of the scenario are granted a turn
+    /// let state = state
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .apply(|state|seq_play::cmd::add_turn(state, 100, 200))
+    ///     .unwrap();
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200]);
     /// ```
     pub fn add_turn(
         mut state: State,
@@ -62,39 +120,141 @@ pub mod cmd {
         if scenario::qry::exists(&state, entity_id) {
             return Err("Can not add turn for scenario entity.".into());
         }
+        grd::no_turn_elsewhere(&state, scenario_id, entity_id)?;
+
+        match scenario::qry::find_entity(&state, entity_id) {
+            Some(captured_by) if captured_by != scenario_id => Err(
+                "Can not add turn for an entity captured by another scenario".into()
+            ),
+            Some(_) => grant_turn(state, scenario_id, entity_id),
+            None => state
+                .apply(|state| scenario::cmd::assign_entity(state, scenario_id, entity_id))
+                .apply(|state| grant_turn(state, scenario_id, entity_id)),
+        }
+    }
+
+    /// COMMAND > Add turns for a set of entities, in order
+    ///
+    /// Every turn is validated before any are added. If one entity can't be
+    /// given a turn, the whole batch fails.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply_with(
+    ///         vec![(200, "A"), (300, "B"), (400, "C")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let party_state = state.clone()
+    ///     .apply( SeqPlay::AddTurns(100, vec![300, 200, 400]) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&party_state, 100), vec![300, 200, 400]);
+    ///
+    /// // A missing entity aborts the batch
+    /// assert!( state.apply( SeqPlay::AddTurns(100, vec![200, 999, 300]) ).is_err() );
+    /// ```
+    pub fn add_turns(
+        state: State,
+        scenario_id: ScenarioId,
+        entity_ids: Vec<EntityId>,
+    ) -> CmdResult<State> {
+        let turns: Vec<SeqPlay> = entity_ids
+            .into_iter()
+            .map(|entity_id| SeqPlay::AddTurn(scenario_id, entity_id))
+            .collect();
+
+        if let Some((index, error)) = turns.validate(&state).into_iter().next() {
+            return Err(format!("Can not add turns, turn {} is invalid: {}", index, error));
+        }
+
+        turns.apply_to(state)
+    }
+
+    /// COMMAND > Grant a turn to an entity that is already a member of a scenario
+    ///
+    /// Unlike `add_turn`, the entity is not captured by the scenario. It must
+    /// already be present as context so that it can join the sequence of play.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()))
+    ///     .apply( Character::Add(300, "BCharacter".into()))
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), Vec::<PubId>::new() );
+    ///
+    /// let state = state
+    ///     .apply(|state|seq_play::cmd::grant_turn(state, 100, 200))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 100), vec![200] );
+    /// assert_eq!(turn_state::qry::get(&state, 200), TurnStatus::Free );
+    ///
+    /// // An entity that isn't a member of the scenario can not be granted a turn
+    /// assert!( state.apply(|state|seq_play::cmd::grant_turn(state, 100, 300)).is_err() );
+    /// ```
+    pub fn grant_turn(
+        mut state: State,
+        scenario_id: ScenarioId,
+        entity_id: EntityId,
+    ) -> CmdResult<State> {
+        if !scenario::qry::exists(&state, scenario_id) {
+            return Err("Can not grant turn in non existent scenario".into());
+        }
+        if scenario::qry::find_entity(&state, entity_id) != Some(scenario_id) {
+            return Err("Can not grant turn to an entity that isn't a member of the scenario".into());
+        }
+        grd::no_turn_elsewhere(&state, scenario_id, entity_id)?;
 
         let turn_state = qry::new_turn_init_state(&state, scenario_id);
 
         state
-            .apply(|state| scenario::cmd::assign_entity(state, scenario_id, entity_id))
             .apply(|state| turn_order::cmd::add_turn(state, scenario_id, entity_id))
-            .apply(|state| turn_state::cmd::set(state, entity_id, turn_state))
+            .apply(|state| turn_state::cmd::set_in(state, scenario_id, entity_id, turn_state))
     }
 
     /// COMMAND > Remove a turn from sequenced play
     ///
+    /// The entity's turn count is reset with its turn. A turn that is added
+    /// again later starts counting from zero.
+    ///
     /// ```
     /// use yourupnext::prelude::*;
     ///
     /// let state = State::default()
     ///     .apply( Scenario::Add(100) )
-    ///     .apply( Character::Add(200, "ACharacter"))
+    ///     .apply( Character::Add(200, "ACharacter".into()))
     ///     .apply(|state|seq_play::cmd::add_turn(state, 100, 200))
-    ///     .apply(|state|seq_play::cmd::remove_turn(state,200));
+    ///     .apply( SeqPlay::Enable(100) )
+    ///     .apply( SeqPlay::NextTurn(100) )
+    ///     .apply( SeqPlay::NextTurn(100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_count::qry::count(&state, 200), 1);
+    ///
+    /// let state = state.apply(|state|seq_play::cmd::remove_turn(state,200));
     ///
     /// assert!(state.is_ok());
     /// let state = state.unwrap();
     /// assert_eq!(turn_state::qry::get(&state, 200), TurnStatus::None );
+    /// assert_eq!(turn_count::qry::count(&state, 200), 0);
     /// ```
-    pub fn remove_turn(mut state: State, entity_id: EntityId) -> CmdResult<State> {
+    pub fn remove_turn(state: State, entity_id: EntityId) -> CmdResult<State> {
         let scenario_id = scenario::qry::find_entity(&state, entity_id);
         if scenario_id.is_none() {
             return Err("Unable to remove turn for entity that isn't in a scenario".into());
         }
-        let scenario_id = scenario_id.unwrap();
-        state
-            .apply(|state| turn_order::cmd::remove_turn(state, scenario_id, entity_id))
-            .apply(|state| turn_state::cmd::set(state, entity_id, TurnStatus::None))
+        // Removing the turn also clears its turn state and count in the scenario
+        state.apply(|state| turn_order::cmd::remove_turn(state, scenario_id.unwrap(), entity_id))
     }
 
     /// Command > Trigger sequenced play mode for a scenario and the entities that have turns
@@ -107,7 +267,7 @@ pub mod cmd {
     ///         Scenario::Add(50)
     ///     ).apply_with(
     ///         vec![(100,"A"),(200,"B"),(300,"C")],
-    ///         |(character_id, name)| Character::Add(character_id, name)
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
     ///     ).apply_with(
     ///         vec![(50,100),(50,200),(50,300)],
     ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
@@ -118,7 +278,14 @@ pub mod cmd {
     /// assert!(state.is_ok() );
     /// let state = state.unwrap();
     /// assert_eq!(turn_state::qry::get(&state, 50), TurnStatus::Active);
-    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Available)
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Available);
+    ///
+    /// // A scenario without turns can not enter sequenced play
+    /// let state = state
+    ///     .apply( Scenario::Add(60) )
+    ///     .apply( SeqPlay::Enable(60) );
+    ///
+    /// assert!(state.is_err());
     /// ```
     pub fn enable(mut state: State, scenario_id: ScenarioId) -> CmdResult<State> {
         if turn_state::qry::get(&state, scenario_id) != TurnStatus::Free {
@@ -127,11 +294,17 @@ pub mod cmd {
             );
         }
         let turns = turn_order::qry::sequence(&state, scenario_id);
+        if turns.is_empty() {
+            return Err("Unable to enter sequenced play for a scenario without turns".into());
+        }
+
+        // Sequenced play starts in the first round
+        state.round_count.update(scenario::qry::id(&state, scenario_id), 1)?;
 
         state
             .apply(|state| turn_state::cmd::set(state, scenario_id, TurnStatus::Active))
             .apply_with(turns, |turn| {
-                move |state| turn_state::cmd::set(state, turn, TurnStatus::Available)
+                move |state| turn_state::cmd::set_in(state, scenario_id, turn, TurnStatus::Available)
             })
     }
 
@@ -144,7 +317,7 @@ pub mod cmd {
     ///         Scenario::Add(50)
     ///     ).apply_with(
     ///         vec![(100,"A"),(200,"B"),(300,"C")],
-    ///         |(character_id, name)| Character::Add(character_id, name)
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
     ///     ).apply_with(
     ///         vec![(50,100),(50,200),(50,300)],
     ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
@@ -167,17 +340,531 @@ pub mod cmd {
 
         let turns = turn_order::qry::sequence(&state, scenario_id);
 
+        let scenario_internal_id = scenario::qry::id(&state, scenario_id);
+        let _ = state.round_count.delete(scenario_internal_id);
+
         state
             .apply(|state| turn_state::cmd::set(state, scenario_id, TurnStatus::Free))
             .apply_with(turns, |turn| {
-                move |state| turn_state::cmd::set(state, turn, TurnStatus::Free)
+                move |state| turn_state::cmd::set_in(state, scenario_id, turn, TurnStatus::Free)
+            })
+    }
+
+    /// COMMAND > Advance sequenced play to the next turn
+    ///
+    /// The `Active` turn is completed and counted, and the next `Available`
+    /// turn in the sequence becomes `Active`. When no turns are available the
+    /// round has run its course and no turn will be active.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply(
+    ///         Scenario::Add(50)
+    ///     ).apply_with(
+    ///         vec![(100,"A"),(200,"B"),(300,"C")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![(50,100),(50,200),(50,300)],
+    ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
+    ///     ).apply(
+    ///         SeqPlay::Enable(50)
+    ///     ).apply(
+    ///         SeqPlay::NextTurn(50)
+    ///     ).unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Active);
+    /// assert_eq!(turn_count::qry::count(&state, 100), 0);
+    ///
+    /// let state = state
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Completed);
+    /// assert_eq!(turn_state::qry::get(&state, 200), TurnStatus::Completed);
+    /// assert_eq!(turn_state::qry::get(&state, 300), TurnStatus::Active);
+    /// assert_eq!(turn_count::qry::count(&state, 100), 1);
+    /// assert_eq!(turn_count::qry::count(&state, 200), 1);
+    /// assert_eq!(turn_count::qry::count(&state, 300), 0);
+    /// assert_eq!(turn_count::qry::total(&state, 50), 2);
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 300), TurnStatus::Completed);
+    /// assert_eq!(turn_count::qry::total(&state, 50), 3);
+    /// ```
+    pub fn next_turn(mut state: State, scenario_id: ScenarioId) -> CmdResult<State> {
+        if !scenario::qry::exists(&state, scenario_id) {
+            return Err("Can not advance the turn of a non existent scenario".into());
+        }
+        if turn_state::qry::get(&state, scenario_id) != TurnStatus::Active {
+            return Err("Can not advance the turn of a scenario that isn't in sequenced play".into());
+        }
+
+        let turns = turn_order::qry::sequence(&state, scenario_id);
+
+        let active_turn = turns.iter()
+            .find(|turn| turn_state::qry::get_in(&state, scenario_id, **turn) == TurnStatus::Active)
+            .copied();
+
+        let state = match active_turn {
+            Some(turn) => state
+                .apply(|state| turn_state::cmd::set_in(state, scenario_id, turn, TurnStatus::Completed))
+                .apply(|state| turn_count::cmd::count_in(state, scenario_id, turn))?,
+            None => state
+        };
+
+        let next_turn = turns.iter()
+            .find(|turn| turn_state::qry::get_in(&state, scenario_id, **turn) == TurnStatus::Available)
+            .copied();
+
+        match next_turn {
+            Some(turn) => turn_state::cmd::set_in(state, scenario_id, turn, TurnStatus::Active),
+            None => Ok(state)
+        }
+    }
+
+    /// COMMAND > Start a new round of sequenced play
+    ///
+    /// Turns that were `Completed`, `Skipped`, or left `Active` become `Available`
+    /// again. `Held` and `Paused` turns carry over. Effects on the scenario's
+    /// members and turns that only last a number of rounds are expired.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply(
+    ///         Scenario::Add(50)
+    ///     ).apply_with(
+    ///         vec![(100,"A"),(200,"B")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![(50,100),(50,200)],
+    ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
+    ///     ).apply(
+    ///         Effect::Add(300, "Bless", 100, 200)
+    ///     ).apply(
+    ///         Effect::SetDuration(300, Duration::UntilNextRound)
+    ///     ).apply(
+    ///         Effect::Add(400, "Curse", 100, 200)
+    ///     ).apply(
+    ///         Effect::SetDuration(400, Duration::ThroughRound(2))
+    ///     ).apply(
+    ///         Effect::Add(500, "Blessing", 100, 200)
+    ///     ).apply(
+    ///         SeqPlay::Enable(50)
+    ///     ).apply(
+    ///         SeqPlay::NextTurn(50)
+    ///     ).apply(
+    ///         SeqPlay::NextTurn(50)
+    ///     ).apply(
+    ///         SeqPlay::NextTurn(50)
+    ///     ).unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Completed);
+    /// assert_eq!(turn_state::qry::get(&state, 200), TurnStatus::Completed);
+    /// assert_eq!(effect::qry::effects_on(&state, 200), vec![300, 400, 500]);
+    ///
+    /// let state = state.apply( SeqPlay::NextRound(50) ).unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Available);
+    /// assert_eq!(turn_state::qry::get(&state, 200), TurnStatus::Available);
+    /// assert_eq!(effect::qry::effects_on(&state, 200), vec![400, 500]);
+    /// assert_eq!(effect::qry::duration(&state, 400), Duration::ThroughRound(1));
+    ///
+    /// let state = state.apply( SeqPlay::NextRound(50) ).unwrap();
+    ///
+    /// assert_eq!(effect::qry::effects_on(&state, 200), vec![500]);
+    /// ```
+    pub fn next_round(mut state: State, scenario_id: ScenarioId) -> CmdResult<State> {
+        if !scenario::qry::exists(&state, scenario_id) {
+            return Err("Can not start a new round in a non existent scenario".into());
+        }
+        if turn_state::qry::get(&state, scenario_id) != TurnStatus::Active {
+            return Err("Can not start a new round in a scenario that isn't in sequenced play".into());
+        }
+
+        let turns = turn_order::qry::sequence(&state, scenario_id);
+
+        let finished_turns: Vec<EntityId> = turns.iter()
+            .filter(|turn| matches!(
+                turn_state::qry::get_in(&state, scenario_id, **turn),
+                TurnStatus::Completed | TurnStatus::Skipped | TurnStatus::Active
+            ))
+            .copied()
+            .collect();
+
+        let mut round_entities = scenario::qry::members(&state, scenario_id);
+        for turn in turns {
+            if !round_entities.contains(&turn) {
+                round_entities.push(turn);
+            }
+        }
+
+        let scenario_internal_id = scenario::qry::id(&state, scenario_id);
+        let round = state.round_count.get_or_default(scenario_internal_id) + 1;
+        state.round_count.update(scenario_internal_id, round)?;
+
+        state
+            .apply_with(finished_turns, |turn| {
+                move |state| turn_state::cmd::set_in(state, scenario_id, turn, TurnStatus::Available)
             })
+            .apply(|state| effect::cmd::expire_round(state, round_entities))
+    }
+
+    /// COMMAND > Apply an initiative roll to an entity
+    ///
+    /// When the scenario is in sequenced play the entity's turn is added (if
+    /// needed) and placed in the sequence by initiative, highest first. Ties are
+    /// placed after the turns that already hold the same initiative.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply(
+    ///         Scenario::Add(50)
+    ///     ).apply_with(
+    ///         vec![(100,"A"),(200,"B"),(300,"C"),(400,"D")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![(100,18),(200,12),(300,5)],
+    ///         |(character_id, rolled)| SeqPlay::RollInitiative(50, character_id, rolled)
+    ///     ).apply_with(
+    ///         vec![(50,100),(50,200),(50,300)],
+    ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
+    ///     ).apply(
+    ///         SeqPlay::Enable(50)
+    ///     ).apply(
+    ///         SeqPlay::NextTurn(50)
+    ///     ).unwrap();
+    ///
+    /// // A late arrival joins the fight mid-combat
+    /// let state = state
+    ///     .apply( SeqPlay::RollInitiative(50, 400, 10) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(initiative::qry::get(&state, 400), Some(10));
+    /// assert_eq!(turn_order::qry::sequence(&state, 50), vec![100, 200, 400, 300]);
+    /// assert_eq!(turn_state::qry::get(&state, 400), TurnStatus::Available);
+    ///
+    /// // A re-roll repositions an existing turn
+    /// let state = state
+    ///     .apply( SeqPlay::RollInitiative(50, 300, 20) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 50), vec![300, 100, 200, 400]);
+    /// ```
+    pub fn roll_initiative(
+        mut state: State,
+        scenario_id: ScenarioId,
+        entity_id: EntityId,
+        rolled: Initiative,
+    ) -> CmdResult<State> {
+        if !scenario::qry::exists(&state, scenario_id) {
+            return Err("Can not roll initiative in a non existent scenario".into());
+        }
+
+        let state = initiative::cmd::set(state, entity_id, rolled)?;
+
+        if turn_state::qry::get(&state, scenario_id) != TurnStatus::Active {
+            return Ok(state);
+        }
+
+        let state = match turn_order::qry::contains(&state, scenario_id, entity_id) {
+            true => state,
+            false => add_turn(state, scenario_id, entity_id)?,
+        };
+
+        let position = qry::initiative_position(&state, scenario_id, entity_id);
+        turn_order::cmd::move_turn(state, scenario_id, entity_id, position)
+    }
+
+    /// COMMAND > Roll initiative for every turn in a scenario
+    ///
+    /// Each turn, in sequence order, rolls `1..=die` from a single seeded
+    /// `Rng`, adding the value of `modifier_stat` when one is given. The turn
+    /// order is then sorted by the results, highest first.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply_with(
+    ///         vec![(100,"A"),(200,"B"),(300,"C")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![100, 200, 300],
+    ///         |character_id| SeqPlay::AddTurn(50, character_id)
+    ///     ).unwrap();
+    ///
+    /// for _ in 0..10 {
+    ///     let rolled = state.clone()
+    ///         .apply( SeqPlay::RollAllInitiative(50, 20, 99, None) )
+    ///         .unwrap();
+    ///     assert_eq!(turn_order::qry::sequence(&rolled, 50), vec![300, 200, 100]);
+    /// }
+    ///
+    /// // A stat can be added to each roll as a modifier
+    /// let state = state
+    ///     .apply( |state| stat::cmd::set_stat(state, 100, "dex", 30) )
+    ///     .apply( SeqPlay::RollAllInitiative(50, 20, 99, Some("dex".to_string())) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_order::qry::sequence(&state, 50), vec![100, 300, 200]);
+    /// assert!(initiative::qry::get(&state, 100).unwrap() > 30);
+    ///
+    /// // A modifier that overflows the roll is an error
+    /// assert!(state.clone()
+    ///     .apply( |state| stat::cmd::set_stat(state, 200, "luck", StatValue::MAX) )
+    ///     .apply( SeqPlay::RollAllInitiative(50, 20, 99, Some("luck".to_string())) )
+    ///     .is_err());
+    ///
+    /// // The scenario must exist
+    /// assert!(state.apply( SeqPlay::RollAllInitiative(60, 20, 99, None) ).is_err());
+    /// ```
+    pub fn roll_all_initiative(
+        state: State,
+        scenario_id: ScenarioId,
+        die: u8,
+        seed: u64,
+        modifier_stat: Option<&str>,
+    ) -> CmdResult<State> {
+        if !scenario::qry::exists(&state, scenario_id) {
+            return Err("Can not roll initiative in a non existent scenario".into());
+        }
+        if die == 0 {
+            return Err("Can not roll initiative with a die that has no sides".into());
+        }
+
+        let mut rng = SeededRng::new(seed);
+        let rolls = turn_order::qry::sequence(&state, scenario_id)
+            .into_iter()
+            .map(|turn| {
+                let modifier = modifier_stat
+                    .map(|stat_name| stat::qry::stat(&state, turn, stat_name))
+                    .unwrap_or_default();
+                match rng.roll(die).checked_add(modifier) {
+                    Some(rolled) => Ok((turn, rolled)),
+                    None => Err(format!("Can not roll initiative for {}, the modifier is out of range", turn)),
+                }
+            })
+            .collect::<CmdResult<Vec<(EntityId, Initiative)>>>()?;
+
+        state
+            .apply_with(rolls, |(turn, rolled)| {
+                move |state| initiative::cmd::set(state, turn, rolled)
+            })
+            .apply(|state| turn_order::cmd::order_by_initiative(state, scenario_id))
+    }
+
+    /// COMMAND > Interrupt an active turn, pausing it until it is resumed
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .apply( SeqPlay::Interrupt(100) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Paused);
+    ///
+    /// // Only active turns can be interrupted
+    /// assert!(state.clone().apply( SeqPlay::Interrupt(100) ).is_err());
+    ///
+    /// let state = state.apply( SeqPlay::Resume(100) ).unwrap();
+    /// assert_eq!(turn_state::qry::get(&state, 100), TurnStatus::Active);
+    ///
+    /// // Only paused turns can be resumed
+    /// assert!(state.apply( SeqPlay::Resume(100) ).is_err());
+    /// ```
+    pub fn interrupt(state: State, entity_id: EntityId) -> CmdResult<State> {
+        if turn_state::qry::get(&state, entity_id) != TurnStatus::Active {
+            return Err("Can not interrupt a turn that isn't active".into());
+        }
+        turn_state::cmd::transition(state, entity_id, TurnStatus::Paused)
+    }
+
+    /// COMMAND > Resume a paused turn, making it active again
+    /// See `seq_play::cmd::interrupt` for tests
+    pub fn resume(state: State, entity_id: EntityId) -> CmdResult<State> {
+        if turn_state::qry::get(&state, entity_id) != TurnStatus::Paused {
+            return Err("Can not resume a turn that isn't paused".into());
+        }
+        turn_state::cmd::transition(state, entity_id, TurnStatus::Active)
     }
 }
 
 pub mod qry {
     use super::*;
 
+    /// QUERY > Get the (scenario, entity) whose turn is up next for the whole session
+    ///
+    /// The primary scenario is the scenario in sequenced play with the lowest
+    /// Public Id. Its `Active` turn is up next, or the next `Available` turn
+    /// when no turn is active.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply(
+    ///         Scenario::Add(50)
+    ///     ).apply_with(
+    ///         vec![(100,"A"),(200,"B")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![(50,100),(50,200)],
+    ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
+    ///     ).unwrap();
+    ///
+    /// assert_eq!(seq_play::qry::up_next(&state), None);
+    ///
+    /// let state = state.apply( SeqPlay::Enable(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::up_next(&state), Some((50, 100)));
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::up_next(&state), Some((50, 100)));
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::up_next(&state), Some((50, 200)));
+    /// ```
+    pub fn up_next(state: &State) -> Option<(ScenarioId, EntityId)> {
+        let scenario_id = state.registry
+            .pub_ids()
+            .into_iter()
+            .find(|pub_id| {
+                scenario::qry::exists(state, *pub_id)
+                    && turn_state::qry::get(state, *pub_id) == TurnStatus::Active
+            })?;
+
+        let entity_id = active(state, scenario_id)
+            .or_else(|| on_deck(state, scenario_id))?;
+
+        Some((scenario_id, entity_id))
+    }
+
+    /// QUERY > Get the entity whose turn is `Active` in a scenario
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply(
+    ///         Scenario::Add(50)
+    ///     ).apply_with(
+    ///         vec![(100,"A"),(200,"B")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![(50,100),(50,200)],
+    ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
+    ///     ).unwrap();
+    ///
+    /// // Play hasn't started
+    /// assert_eq!(seq_play::qry::active(&state, 50), None);
+    /// assert_eq!(seq_play::qry::on_deck(&state, 50), None);
+    ///
+    /// let state = state.apply( SeqPlay::Enable(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::active(&state, 50), None);
+    /// assert_eq!(seq_play::qry::on_deck(&state, 50), Some(100));
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::active(&state, 50), Some(100));
+    /// assert_eq!(seq_play::qry::on_deck(&state, 50), Some(200));
+    /// ```
+    pub fn active(state: &State, scenario_id: ScenarioId) -> Option<EntityId> {
+        first_in_status(state, scenario_id, TurnStatus::Active)
+    }
+
+    /// QUERY > Get the entity whose turn will be activated by the next `NextTurn`,
+    /// the first `Available` turn in sequence
+    /// See `seq_play::qry::active` for tests
+    pub fn on_deck(state: &State, scenario_id: ScenarioId) -> Option<EntityId> {
+        first_in_status(state, scenario_id, TurnStatus::Available)
+    }
+
+    fn first_in_status(state: &State, scenario_id: ScenarioId, status: TurnStatus) -> Option<EntityId> {
+        if turn_state::qry::get(state, scenario_id) != TurnStatus::Active {
+            return None;
+        }
+        turn_state::qry::all_in(state, scenario_id, status)
+            .first()
+            .copied()
+    }
+
+    /// QUERY > Get the turns earlier in the sequence than an entity that have yet
+    /// to act this round
+    ///
+    /// A turn that is `Available`, `Active`, `Paused`, or `Held` has not yet acted.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply(
+    ///         Scenario::Add(50)
+    ///     ).apply_with(
+    ///         vec![(100,"A"),(200,"B"),(300,"C"),(400,"D")],
+    ///         |(character_id, name)| Character::Add(character_id, name.into())
+    ///     ).apply_with(
+    ///         vec![(50,100),(50,200),(50,300),(50,400)],
+    ///         |(scenario_id, character_id)| SeqPlay::AddTurn(scenario_id, character_id)
+    ///     ).apply(
+    ///         SeqPlay::Enable(50)
+    ///     ).apply(
+    ///         SeqPlay::NextTurn(50)
+    ///     ).unwrap();
+    ///
+    /// assert_eq!(seq_play::qry::acts_before(&state, 50, 300), vec![100, 200]);
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    ///
+    /// assert_eq!(seq_play::qry::acts_before(&state, 50, 300), vec![200]);
+    /// assert_eq!(seq_play::qry::acts_before(&state, 50, 100), Vec::<PubId>::new());
+    ///
+    /// // Entities without a turn in the scenario have nobody acting before them
+    /// assert_eq!(seq_play::qry::acts_before(&state, 50, 500), Vec::<PubId>::new());
+    /// ```
+    pub fn acts_before(state: &State, scenario_id: ScenarioId, entity_id: EntityId) -> Vec<EntityId> {
+        if !turn_order::qry::contains(state, scenario_id, entity_id) {
+            return Vec::with_capacity(0);
+        }
+        turn_order::qry::sequence(state, scenario_id)
+            .into_iter()
+            .take_while(|turn| *turn != entity_id)
+            .filter(|turn| matches!(
+                turn_state::qry::get_in(state, scenario_id, *turn),
+                TurnStatus::Available | TurnStatus::Active | TurnStatus::Paused | TurnStatus::Held(_)
+            ))
+            .collect()
+    }
+
+    /// QUERY > Get the position within a scenario's sequence of turns where an
+    /// entity belongs based on its initiative
+    /// See `seq_play::cmd::roll_initiative` for tests
+    pub fn initiative_position(state: &State, scenario_id: ScenarioId, entity_id: EntityId) -> TurnPosition {
+        let rolled = initiative::qry::get(state, entity_id);
+
+        let anchor = turn_order::qry::sequence(state, scenario_id)
+            .into_iter()
+            .filter(|turn| *turn != entity_id)
+            .find(|turn| initiative::qry::get(state, *turn) < rolled);
+
+        match anchor {
+            Some(anchor) => TurnPosition::Before(anchor),
+            None => TurnPosition::End,
+        }
+    }
+
     /// QUERY > Get the turn state of new turns added to a scenario
     pub fn new_turn_init_state(state: &State, scenario_id: ScenarioId) -> TurnStatus {
         match turn_state::qry::get(state, scenario_id) {
@@ -185,4 +872,291 @@ pub mod qry {
             _ => TurnStatus::Available,
         }
     }
+
+    /// QUERY > Get the round of sequenced play a scenario is in, starting at 1
+    /// when play is enabled. Scenarios in free play are in round 0.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( Character::Add(200, "B".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::AddTurn(50, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(seq_play::qry::round(&state, 50), 0);
+    ///
+    /// let state = state.apply( SeqPlay::Enable(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::round(&state, 50), 1);
+    /// assert_eq!(seq_play::qry::turn_in_round(&state, 50), 0);
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::turn_in_round(&state, 50), 1);
+    ///
+    /// let state = state.apply( SeqPlay::NextTurn(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::turn_in_round(&state, 50), 2);
+    ///
+    /// // Two full rounds
+    /// let state = state
+    ///     .apply( SeqPlay::NextRound(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .unwrap();
+    /// assert_eq!(seq_play::qry::round(&state, 50), 2);
+    /// assert_eq!(seq_play::qry::turn_in_round(&state, 50), 1);
+    ///
+    /// let state = state
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .apply( SeqPlay::NextRound(50) )
+    ///     .unwrap();
+    /// assert_eq!(seq_play::qry::round(&state, 50), 3);
+    /// assert_eq!(seq_play::qry::turn_in_round(&state, 50), 0);
+    ///
+    /// let state = state.apply( SeqPlay::Disable(50) ).unwrap();
+    /// assert_eq!(seq_play::qry::round(&state, 50), 0);
+    /// ```
+    pub fn round(state: &State, scenario_id: ScenarioId) -> usize {
+        state.round_count.get_or_default(scenario::qry::id(state, scenario_id))
+    }
+
+    /// QUERY > Get how many turns have started in a scenario's current round,
+    /// counting the `Active` turn along with `Completed` and `Skipped` turns
+    /// See `seq_play::qry::round` for tests
+    pub fn turn_in_round(state: &State, scenario_id: ScenarioId) -> usize {
+        turn_order::qry::sequence(state, scenario_id)
+            .into_iter()
+            .filter(|turn| matches!(
+                turn_state::qry::get_in(state, scenario_id, *turn),
+                TurnStatus::Active | TurnStatus::Completed | TurnStatus::Skipped
+            ))
+            .count()
+    }
+
+    /// QUERY > Get the entities that have a turn in a scenario's sequence but
+    /// aren't captured by any scenario
+    ///
+    /// Turns are only granted to members of a scenario, so an orphaned turn means
+    /// the data has drifted, e.g. in a hand edited save file.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( Character::Add(200, "B".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::AddTurn(50, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(seq_play::qry::orphaned_turns(&state), Vec::<PubId>::new());
+    ///
+    /// // Corrupt the membership of 200 without touching its turn
+    /// let mut state = state;
+    /// let id = entity::qry::id(&state, 200);
+    /// state.scenario_entity.remove_parent(id).unwrap();
+    ///
+    /// assert_eq!(seq_play::qry::orphaned_turns(&state), vec![200]);
+    /// ```
+    pub fn orphaned_turns(state: &State) -> Vec<EntityId> {
+        let mut orphans: Vec<EntityId> = state::qry::turn_order_orphans(state)
+            .into_iter()
+            .map(|(_, entity_id)| entity_id)
+            .filter(|entity_id| !state.scenario_entity.is_child(entity::qry::id(state, *entity_id)))
+            .collect();
+        orphans.sort();
+        orphans.dedup();
+        orphans
+    }
+
+    /// QUERY > Get a summary of the turns in a scenario, in turn order
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( Character::Add(200, "B".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::AddTurn(50, 200) )
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .unwrap();
+    ///
+    /// let summary = seq_play::qry::summary(&state, 50);
+    ///
+    /// assert_eq!(summary.len(), 2);
+    /// assert_eq!(summary[0], TurnInfo {
+    ///     pub_id: 100,
+    ///     name: "A".to_string(),
+    ///     status: TurnStatus::Completed,
+    ///     turn_count: 1,
+    ///     position: 1,
+    /// });
+    /// assert_eq!(summary[1].status, TurnStatus::Active);
+    /// assert_eq!(summary[1].position, 2);
+    /// ```
+    pub fn summary(state: &State, scenario_id: ScenarioId) -> Vec<TurnInfo> {
+        turn_order::qry::sequence(state, scenario_id)
+            .into_iter()
+            .enumerate()
+            .map(|(index, turn)| TurnInfo {
+                pub_id: turn,
+                name: name::qry::get(state, turn),
+                status: turn_state::qry::get_in(state, scenario_id, turn),
+                turn_count: turn_count::qry::count_in(state, scenario_id, turn),
+                position: index + 1,
+            })
+            .collect()
+    }
+
+    /// QUERY > Get a JSON tracker of the turns in a scenario, in turn order, for frontends
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( Character::Add(200, "B".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::AddTurn(50, 200) )
+    ///     .apply( |state| initiative::cmd::set(state, 100, 12) )
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     seq_play::qry::tracker_json(&state, 50),
+    ///     concat!(
+    ///         r#"[{"pub_id":100,"name":"A","state":"Active","turn_count":0,"initiative":12},"#,
+    ///         r#"{"pub_id":200,"name":"B","state":"Available","turn_count":0,"initiative":null}]"#
+    ///     )
+    /// );
+    /// ```
+    pub fn tracker_json(state: &State, scenario_id: ScenarioId) -> String {
+        #[derive(Serialize)]
+        struct TrackerEntry {
+            pub_id: PubId,
+            name: String,
+            state: TurnStatus,
+            turn_count: TurnCount,
+            initiative: Option<Initiative>,
+        }
+
+        let entries: Vec<TrackerEntry> = turn_order::qry::sequence(state, scenario_id)
+            .into_iter()
+            .map(|turn| TrackerEntry {
+                pub_id: turn,
+                name: name::qry::get(state, turn),
+                state: turn_state::qry::get_in(state, scenario_id, turn),
+                turn_count: turn_count::qry::count_in(state, scenario_id, turn),
+                initiative: initiative::qry::get(state, turn),
+            })
+            .collect();
+
+        serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+/// ## Sequenced Play Guards
+pub mod grd {
+    use super::*;
+
+    /// GUARD > Confirm that an entity doesn't have a turn in a scenario other than `scenario_id`
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Scenario::Add(60) )
+    ///     .apply( Character::Add(100, "A".into()) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .unwrap();
+    ///
+    /// assert!(seq_play::grd::no_turn_elsewhere(&state, 50, 100).is_ok());
+    /// assert!(seq_play::grd::no_turn_elsewhere(&state, 60, 100).is_err());
+    ///
+    /// // An entity can't take turns in two scenarios at once
+    /// assert!(state.apply( SeqPlay::AddTurn(60, 100) ).is_err());
+    /// ```
+    pub fn no_turn_elsewhere(state: &State, scenario_id: ScenarioId, entity_id: EntityId) -> CmdResult<()> {
+        match turn_order::qry::scenario_of(state, entity_id) {
+            Some(other_scenario_id) if other_scenario_id != scenario_id => Err(format!(
+                "Entity {} already has a turn in scenario {}",
+                entity_id, other_scenario_id
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// ## Sequenced Play Exports
+pub mod export {
+    use super::*;
+
+    /// EXPORT > Export a scenario's turns as CSV
+    ///
+    /// Rows follow the turn summary with the columns
+    /// `position,pub_id,name,status,turn_count`. Names containing commas,
+    /// quotes or line breaks are quoted; missing names are left empty.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(50) )
+    ///     .apply( Character::Add(100, "Aria".into()) )
+    ///     .apply( Character::Add(200, "Brom, the \"Bold\"".into()) )
+    ///     .apply( Entity::Add(300) )
+    ///     .apply( SeqPlay::AddTurn(50, 100) )
+    ///     .apply( SeqPlay::AddTurn(50, 200) )
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 50, 300) )
+    ///     .apply( SeqPlay::Enable(50) )
+    ///     .apply( SeqPlay::NextTurn(50) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     seq_play::export::to_csv(&state, 50),
+    ///     [
+    ///         "position,pub_id,name,status,turn_count",
+    ///         "1,100,Aria,Active,0",
+    ///         "2,200,\"Brom, the \"\"Bold\"\"\",Available,0",
+    ///         "3,300,,Available,0",
+    ///         "",
+    ///     ].join("\n")
+    /// );
+    ///
+    /// // A scenario without turns only has a header
+    /// assert_eq!(
+    ///     seq_play::export::to_csv(&state, 999),
+    ///     "position,pub_id,name,status,turn_count\n"
+    /// );
+    /// ```
+    pub fn to_csv(state: &State, scenario_id: ScenarioId) -> String {
+        let mut csv = "position,pub_id,name,status,turn_count\n".to_string();
+
+        for turn in qry::summary(state, scenario_id) {
+            csv.push_str(&format!(
+                "{},{},{},{:?},{}\n",
+                turn.position,
+                turn.pub_id,
+                csv_field(&turn.name),
+                turn.status,
+                turn.turn_count,
+            ));
+        }
+
+        csv
+    }
+
+    fn csv_field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
 }
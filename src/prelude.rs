@@ -3,6 +3,8 @@ pub use crate::{
     state,
     state::{
         State,
+        StateDiff,
+        ComponentDiff,
     },
     error,
     error:: {
@@ -20,13 +22,26 @@ pub use crate::{
         CmdErr,
         Applicable,
         ApplicableChainable,
-        ApplicableWithChainable
+        ApplicableWithChainable,
+        ApplicableValidatable
     },
+    journal::{
+        JournaledState,
+        JournaledChainable
+    },
+    dice::{
+        Rng,
+        SeededRng
+    },
+    event,
+    event::DomainEvent,
     queryable::{
         QueryResult,
         QueryError,
         Queryable,
-        ApplicableQueryResult
+        QueryableEach,
+        ApplicableQueryResult,
+        ApplicableEachQueryResult
     },
     model::{
         entity,
@@ -41,8 +56,23 @@ pub use crate::{
         scenario,
         scenario::ScenarioId,
         scenario::Scenario,
+        scenario::PlayMode,
         seq_play,
-        seq_play::SeqPlay
+        seq_play::SeqPlay,
+        seq_play::TurnInfo,
+        effect,
+        effect::EffectId,
+        effect::Effect,
+        effect::Duration,
+        link,
+        link::Link,
+        link::Relation,
+        item,
+        item::ItemId,
+        item::Item,
+        location,
+        location::LocationId,
+        location::Location,
     },
 
     registry::{
@@ -57,6 +87,7 @@ pub use crate::{
 
         // Collection types
         component::Component,
+        history::History,
 
         // Components
         entity_type,
@@ -76,6 +107,7 @@ pub use crate::{
 
         turn_state,
         turn_state::{
+            TurnKey,
             TurnStatus
         },
 
@@ -89,6 +121,24 @@ pub use crate::{
             TurnOrder,
             TurnPosition
         },
+
+        initiative,
+        initiative::{
+            Initiative
+        },
+
+        tags,
+        tags::{
+            Tag
+        },
+
+        stat,
+        stat::{
+            StatKey,
+            StatStash,
+            StatValue,
+            Stats
+        },
     },
 
 };
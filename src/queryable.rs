@@ -6,6 +6,7 @@ pub type QueryResult<QueriedData> = Result<(State, QueriedData), QueryError>;
 
 pub trait Queryable<QueryData, F> {
     fn query(self, query_fn: F) -> QueryResult<QueryData>;
+    fn query_ref(&self, query_fn: F) -> Result<QueryData, QueryError>;
 }
 
 impl<QueryData, F: Fn(&State) -> Result<QueryData, QueryError>> Queryable<QueryData, F> for State {
@@ -37,6 +38,63 @@ impl<QueryData, F: Fn(&State) -> Result<QueryData, QueryError>> Queryable<QueryD
         let response = query_fn(&self)?;
         Ok((self, response))
     }
+
+    /// Borrowing queries for when chaining isn't needed
+    ///
+    /// Runs the same kind of query function as `query` but only borrows State,
+    /// returning the queried data on its own.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .unwrap();
+    ///
+    /// let name = state.query_ref(|state| Ok(character::qry::name(state, 100)));
+    /// assert_eq!(name, Ok("ACharacter".to_string()));
+    ///
+    /// // State is still available after the query
+    /// assert!(character::qry::exists(&state, 100));
+    /// ```
+    fn query_ref(&self, query_fn: F) -> Result<QueryData, QueryError> {
+        query_fn(self)
+    }
+}
+
+pub trait QueryableEach<Item, F> {
+    fn qry_each(self, query_fn: F) -> QueryResult<Vec<Item>>;
+}
+
+impl<Item, F: Fn(&State) -> Vec<Item>> QueryableEach<Item, F> for State {
+    /// State queries with collection return types
+    ///
+    /// Many queries return a Vec of results, e.g. the Public Ids of a player's
+    /// characters. `qry_each` wraps those queries in a QueryResult so that
+    /// the collection can be carried forward and consumed item by item with
+    /// `apply_with_each`.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( Character::AssignPlayer(200, 100) )
+    ///     .apply( Character::AssignPlayer(300, 100) )
+    ///     .unwrap()
+    ///     .qry_each(|state| player::qry::characters_of(state, 100))
+    ///     .apply_with_each(|character_pub_id| Character::Rename(character_pub_id, "Renamed".into()))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::name(&state, 200), "Renamed".to_string());
+    /// assert_eq!(character::qry::name(&state, 300), "Renamed".to_string());
+    /// ```
+    fn qry_each(self, query_fn: F) -> QueryResult<Vec<Item>> {
+        let response = query_fn(&self);
+        Ok((self, response))
+    }
 }
 
 pub trait ApplicableQueryResult<QueriedData> {
@@ -51,7 +109,7 @@ impl<QueriedData> ApplicableQueryResult<QueriedData> for QueryResult<QueriedData
     ///
     /// let state = State::default()
     ///     .query( |state| Ok("character name") )
-    ///     .apply_with( |character_name| Character::Add(100, character_name) );
+    ///     .apply_with( |character_name| Character::Add(100, character_name.into()) );
     ///
     /// assert!(state.is_ok());
     /// ```
@@ -65,3 +123,59 @@ impl<QueriedData> ApplicableQueryResult<QueriedData> for QueryResult<QueriedData
         }
     }
 }
+
+pub trait ApplicableEachQueryResult<Item> {
+    #[deprecated(note = "use apply_with_each")]
+    fn apply_each<Applicator: Applicable, F: Fn(Item) -> Applicator>(self, make_applicable: F) -> CmdResult<State>;
+    fn apply_with_each<Applicator: Applicable, F: Fn(Item) -> Applicator>(self, make_applicable: F) -> CmdResult<State>;
+}
+
+impl<Item> ApplicableEachQueryResult<Item> for QueryResult<Vec<Item>> {
+    /// Allows a chainable command for each item of a collection query.
+    /// Use `apply_with_each`.
+    fn apply_each<Applicator: Applicable, F: Fn(Item) -> Applicator>(
+        self,
+        applicator_factory: F,
+    ) -> CmdResult<State> {
+        self.apply_with_each(applicator_factory)
+    }
+
+    /// Allows a chainable command for each item of a query that returns a Vec.
+    /// Commands are applied in order and the first error stops the chain.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Player::Add(100, "APlayer".into()) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( Character::AssignPlayer(200, 100) )
+    ///     .apply( Character::AssignPlayer(300, 100) )
+    ///     .unwrap()
+    ///     .query( |state| Ok(player::qry::characters_of(state, 100)) )
+    ///     .apply_with_each( |character_pub_id| Character::Rename(character_pub_id, format!("Hero {}", character_pub_id).into()) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(character::qry::name(&state, 200), "Hero 200".to_string());
+    /// assert_eq!(character::qry::name(&state, 300), "Hero 300".to_string());
+    ///
+    /// let state = state
+    ///     .query( |state| Ok(vec![400, 200]) )
+    ///     .apply_with_each( |pub_id| Character::Add(pub_id, "ACharacter".into()) );
+    ///
+    /// assert!(state.is_err());
+    /// ```
+    fn apply_with_each<Applicator: Applicable, F: Fn(Item) -> Applicator>(
+        self,
+        applicator_factory: F,
+    ) -> CmdResult<State> {
+        match self {
+            Ok((state, queried_data)) => queried_data
+                .into_iter()
+                .map(applicator_factory)
+                .collect::<Vec<Applicator>>()
+                .apply_to(state),
+            Err(e) => CmdResult::Err(e),
+        }
+    }
+}
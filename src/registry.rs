@@ -6,9 +6,22 @@ pub type Id = usize;
 
 pub type PubId = usize;
 
+/// A snapshot of how the registry's id space is being used
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RegistryStats {
+    /// The number of registered entities
+    pub live_count: usize,
+    /// The Id that the next registered entity will receive
+    pub next_id: Id,
+    /// Ids freed by deregistering that can be reused. Ids aren't recycled
+    /// yet, so this is always 0.
+    pub recycled_available: usize,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Registry {
     next_id: Id,
+    pub_high_water: PubId,
     id_dict: HashMap<Id, PubId>,
     pub_dict: HashMap<PubId, Id>,
 }
@@ -17,6 +30,7 @@ impl Default for Registry {
     fn default() -> Self {
         Self {
             next_id: 1,
+            pub_high_water: 0,
             id_dict: HashMap::default(),
             pub_dict: HashMap::default(),
         }
@@ -42,9 +56,45 @@ impl Registry {
     pub fn pub_id(&self, id: &Id) -> Option<PubId> {
         self.id_dict.get(id).map(|pub_id| *pub_id)
     }
+
+    pub fn len(&self) -> usize {
+        self.pub_dict.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pub_dict.is_empty()
+    }
+
+    pub fn pub_ids(&self) -> Vec<PubId> {
+        let mut pub_ids: Vec<PubId> = self.pub_dict.keys().copied().collect();
+        pub_ids.sort();
+        pub_ids
+    }
 }
 
+/// Register an entity under a Public Id
+///
+/// Public Id 0 is reserved. Queries such as `entity::qry::id` return 0 to
+/// mean "not found", so it can't be used by a real entity.
+///
+/// ```
+/// use yourupnext::prelude::*;
+/// use yourupnext::registry;
+///
+/// let state = registry::register(State::default(), 100).unwrap();
+/// assert_eq!(registry::id(&state, 100), 1);
+///
+/// assert!( registry::register(state.clone(), 100).is_err() );
+/// assert_eq!(
+///     registry::register(state, 0),
+///     Err("Public Id 0 is reserved and can not be registered.".to_string())
+/// );
+/// assert!( Character::Add(0, "ACharacter".into()).apply_to_default().is_err() );
+/// ```
 pub fn register(mut state: State, pub_id: PubId) -> CmdResult<State> {
+    if pub_id == 0 {
+        return Err("Public Id 0 is reserved and can not be registered.".to_string());
+    }
     if state.registry.has_pub_id(&pub_id) {
         return Err("Entity with PUBLIC ID already exists.".to_string());
     }
@@ -52,6 +102,7 @@ pub fn register(mut state: State, pub_id: PubId) -> CmdResult<State> {
     state.registry.id_dict.insert(state.registry.next_id, pub_id.clone());
     state.registry.pub_dict.insert(pub_id, state.registry.next_id);
     state.registry.next_id += 1;
+    state.registry.pub_high_water = state.registry.pub_high_water.max(pub_id);
 
     Ok(state)
 }
@@ -74,6 +125,89 @@ pub fn deregister(mut state: State, id: Id) -> CmdResult<State> {
     Ok(state)
 }
 
+/// Reserve a contiguous block of Public Ids
+///
+/// The registry tracks a high-water mark: the largest Public Id that has
+/// been registered or reserved. A block is allocated directly above the
+/// mark and the mark moves to the end of the block. The mark never moves
+/// down, even when entities are deregistered, so a reserved block never
+/// overlaps a later block or any Public Id registered before it was reserved.
+/// Reserving doesn't register the ids.
+///
+/// ```
+/// use yourupnext::prelude::*;
+/// use yourupnext::registry;
+///
+/// let state = State::default()
+///     .apply( Character::Add(100, "ACharacter".into()) )
+///     .unwrap();
+///
+/// let (state, first) = registry::reserve_block(state, 2).unwrap();
+/// let (state, second) = registry::reserve_block(state, 3).unwrap();
+///
+/// assert_eq!(first, vec![101, 102]);
+/// assert_eq!(second, vec![103, 104, 105]);
+/// assert!(first.iter().all(|pub_id| !second.contains(pub_id)));
+///
+/// // Reserved ids are free to be registered
+/// let state = state
+///     .apply_with(first, |pub_id| Character::Add(pub_id, "Reserved".into()))
+///     .apply( Character::Add(500, "BCharacter".into()) )
+///     .unwrap();
+///
+/// let (state, third) = registry::reserve_block(state, 1).unwrap();
+/// assert_eq!(third, vec![501]);
+///
+/// assert!(registry::reserve_block(state.clone(), 0).is_err());
+///
+/// // Nothing can be reserved past the largest Public Id
+/// let state = state
+///     .apply( Character::Add(PubId::MAX, "CCharacter".into()) )
+///     .unwrap();
+///
+/// assert!(registry::reserve_block(state, 1).is_err());
+/// ```
+pub fn reserve_block(mut state: State, count: usize) -> CmdResult<(State, Vec<PubId>)> {
+    if count == 0 {
+        return Err("Can not reserve an empty block of PUBLIC IDs.".to_string());
+    }
+    let past_the_end = "Can not reserve a block of PUBLIC IDs past the largest PUBLIC ID.";
+    let start = state.registry.pub_high_water
+        .checked_add(1)
+        .ok_or(past_the_end)?;
+    let end = start
+        .checked_add(count)
+        .ok_or(past_the_end)?;
+
+    state.registry.pub_high_water = end - 1;
+    Ok((state, (start..end).collect()))
+}
+
+/// Deregister an entity by its Public Id
+/// ```
+/// use yourupnext::prelude::*;
+/// use yourupnext::registry;
+///
+/// let state = registry::register(State::default(), 100).unwrap();
+/// let id = registry::id(&state, 100);
+///
+/// let state = registry::deregister_pub(state, 100).unwrap();
+/// assert!( !state.registry.has_pub_id(&100) );
+/// assert!( !state.registry.has_id(&id) );
+///
+/// assert_eq!(
+///     registry::deregister_pub(state, 100),
+///     Err("Unable to remove entity, unknown PUBLIC ID 100.".to_string())
+/// );
+/// ```
+pub fn deregister_pub(state: State, pub_id: PubId) -> CmdResult<State> {
+    if !state.registry.has_pub_id(&pub_id) {
+        return Err(format!("Unable to remove entity, unknown PUBLIC ID {}.", pub_id));
+    }
+    let id = state.registry.id(&pub_id);
+    deregister(state, id)
+}
+
 // ----------------------------------------------------------------------
 // Query
 // ----------------------------------------------------------------------
@@ -87,3 +221,44 @@ pub fn id(state: &State, pub_id: PubId) -> Id {
 pub fn pub_id(state: &State, id: Id) -> Option<PubId> {
     state.registry.pub_id(&id)
 }
+
+pub mod qry {
+    use super::*;
+
+    /// QUERY > Get diagnostics about the registry's id space
+    /// ```
+    /// use yourupnext::prelude::*;
+    /// use yourupnext::registry;
+    ///
+    /// let state = State::default();
+    /// let stats = registry::qry::stats(&state);
+    /// assert_eq!(stats.live_count, 0);
+    /// assert_eq!(stats.next_id, 1);
+    ///
+    /// let state = state
+    ///     .apply( Character::Add(100, "ACharacter".into()) )
+    ///     .apply( Character::Add(200, "BCharacter".into()) )
+    ///     .apply( Scenario::Add(300) )
+    ///     .unwrap();
+    ///
+    /// let stats = registry::qry::stats(&state);
+    /// assert_eq!(stats.live_count, 3);
+    /// assert_eq!(stats.next_id, 4);
+    ///
+    /// // Removing an entity doesn't give its Id back
+    /// let state = state.apply( Character::Remove(100) ).unwrap();
+    ///
+    /// assert_eq!(registry::qry::stats(&state), registry::RegistryStats {
+    ///     live_count: 2,
+    ///     next_id: 4,
+    ///     recycled_available: 0,
+    /// });
+    /// ```
+    pub fn stats(state: &State) -> RegistryStats {
+        RegistryStats {
+            live_count: state.registry.len(),
+            next_id: state.registry.next_id,
+            recycled_available: 0,
+        }
+    }
+}
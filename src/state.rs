@@ -1,5 +1,12 @@
 use crate::prelude::*;
+use crate::registry;
 use crate::registry::Registry;
+use crate::component::component::{ComponentKey, ComponentValue};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A serializable projection of state for UIs
+pub mod view;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct State {
@@ -8,14 +15,28 @@ pub struct State {
 
     pub name: Component<String>,
     pub description: Component<String>,
-    pub turn_state: Component<TurnStatus>,
-    pub turn_count: Component<TurnCount>,
+    pub turn_state: History<TurnStatus, TurnKey>,
+    pub turn_count: Component<TurnCount, TurnKey>,
     pub turn_order: Component<TurnOrder>,
+    pub round_count: Component<usize>,
+    pub initiative: Component<Initiative>,
+    pub tags: Component<Vec<String>>,
+    pub stats: History<StatValue, StatKey>,
+    pub stat_stash: Component<StatStash>,
 
     pub character_player: Hierarchy,
 
     pub scenario_entity: Hierarchy,
 
+    pub effect_source: Hierarchy,
+    pub effect_target: Hierarchy,
+    pub effect_duration: Component<Duration>,
+
+    pub item_owner: Hierarchy,
+    pub entity_location: Hierarchy,
+
+    pub links: HashMap<String, Association>,
+
 }
 
 impl Default for State {
@@ -25,14 +46,740 @@ impl Default for State {
             entity_type: Component::default(),
             name: Component::default(),
             description: Component::default(),
-            turn_state: Component::default(),
+            turn_state: History::default(),
             turn_count: Component::default(),
             turn_order: Component::default(),
+            round_count: Component::default(),
+            initiative: Component::default(),
+            tags: Component::default(),
+            stats: History::default(),
+            stat_stash: Component::default(),
 
             character_player: Hierarchy::default(),
             scenario_entity: Hierarchy::default(),
+            effect_source: Hierarchy::default(),
+            effect_target: Hierarchy::default(),
+            effect_duration: Component::default(),
+            item_owner: Hierarchy::default(),
+            entity_location: Hierarchy::default(),
+            links: HashMap::default(),
+        }
+    }
+}
+
+impl State {
+    /// Opt in to remembering the last `capacity` values of the `turn_state`
+    /// and `stat` components. See `stat::qry::history` and
+    /// `turn_state::qry::history`.
+    pub fn track_history(mut self, capacity: usize) -> Self {
+        self.turn_state.set_capacity(capacity);
+        self.stats.set_capacity(capacity);
+        self
+    }
+
+    /// A readable listing of every entity, one per line, in ascending Public Id order.
+    /// Each line has the entity's Public Id, type, name, and the entities it belongs to.
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Scenario::Rename(100, "Campaign".into()) )
+    ///     .apply( Scenario::CaptureEntity(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     state.dump(),
+    ///     "100 Scenario \"Campaign\"\n200 Character \"ACharacter\" scenario=100"
+    /// );
+    /// assert_eq!(state.to_string(), state.dump());
+    /// ```
+    pub fn dump(&self) -> String {
+        entity::qry::all(self)
+            .into_iter()
+            .map(|pub_id| {
+                let memberships = [
+                    ("scenario", scenario::qry::find_entity(self, pub_id)),
+                    ("player", character::qry::player(self, pub_id)),
+                    ("owner", item::qry::owner(self, pub_id)),
+                    ("location", location::qry::where_is(self, pub_id)),
+                ];
+
+                let mut line = format!(
+                    "{} {:?} {:?}",
+                    pub_id,
+                    entity_type::qry::get(self, pub_id),
+                    name::qry::get(self, pub_id)
+                );
+                for (membership, parent) in memberships {
+                    if let Some(parent) = parent {
+                        line.push_str(&format!(" {}={}", membership, parent));
+                    }
+                }
+                line
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.dump())
+    }
+}
+
+/// COMMAND > Remove an entity's internal `Id` from every component and hierarchy
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let state = State::default()
+///     .apply( Scenario::Add(100) )
+///     .apply( Player::Add(200, "APlayer".into()) )
+///     .apply( Character::Add(300, "ACharacter".into()) )
+///     .apply( Entity::Describe(300, "A description") )
+///     .apply( Character::AssignPlayer(300, 200) )
+///     .apply( SeqPlay::AddTurn(100, 300) )
+///     .apply( |state| turn_count::cmd::count(state, 300) )
+///     .apply( |state| initiative::cmd::set(state, 300, 10) )
+///     .apply( |state| tags::cmd::add_tag(state, 300, "boss") )
+///     .apply( |state| stat::cmd::set_stat(state, 300, "hp", 20) )
+///     .apply( |state| character::cmd::stash_attributes(state, 300, "polymorph") )
+///     .apply( Link::Assign("rival", 300, 200) )
+///     .unwrap();
+///
+/// let id = entity::qry::id(&state, 300);
+/// let state = state::purge_entity(state, id).unwrap();
+///
+/// assert!( !state.entity_type.is_set(id) );
+/// assert!( !state.name.is_set(id) );
+/// assert!( !state.description.is_set(id) );
+/// assert!( state.turn_state.iter().all(|((_, turn_id), _)| turn_id != id) );
+/// assert!( state.turn_count.iter().all(|((_, turn_id), _)| turn_id != id) );
+/// assert!( !state.initiative.is_set(id) );
+/// assert!( !state.tags.is_set(id) );
+/// assert!( state.stats.iter().all(|((stat_id, _), _)| stat_id != id) );
+/// assert!( !state.stat_stash.is_set(id) );
+/// assert!( !state.character_player.is_child(id) );
+/// assert!( !state.scenario_entity.is_child(id) );
+/// assert!( state.turn_order.iter().all(|(_, turns)| !turns.contains(&id)) );
+/// assert!( state.links.values().all(|links| !links.has_assoc(&id) && !links.is_assoc_id(&id)) );
+/// ```
+pub fn purge_entity(mut state: State, id: Id) -> CmdResult<State> {
+    state.entity_type.retain(|component_id, _| *component_id != id);
+    state.name.retain(|component_id, _| *component_id != id);
+    state.description.retain(|component_id, _| *component_id != id);
+    state.turn_state.retain(|(scenario_id, component_id), _| *scenario_id != id && *component_id != id);
+    state.turn_count.retain(|(scenario_id, component_id), _| *scenario_id != id && *component_id != id);
+    state.initiative.retain(|component_id, _| *component_id != id);
+    state.tags.retain(|component_id, _| *component_id != id);
+    state.stats.retain(|(component_id, _), _| *component_id != id);
+    state.stat_stash.retain(|component_id, _| *component_id != id);
+    state.effect_duration.retain(|component_id, _| *component_id != id);
+
+    state.turn_order.retain(|component_id, _| *component_id != id);
+    state.round_count.retain(|component_id, _| *component_id != id);
+    let sequences: Vec<(Id, TurnOrder)> = state.turn_order
+        .iter()
+        .filter(|(_, turns)| turns.contains(&id))
+        .map(|(scenario_id, turns)| (scenario_id, turns.clone()))
+        .collect();
+    for (scenario_id, mut turns) in sequences {
+        turns.retain(|turn| *turn != id);
+        state.turn_order.update(scenario_id, turns)?;
+    }
+
+    state.character_player.remove(id)?;
+    state.scenario_entity.remove(id)?;
+    state.effect_source.remove(id)?;
+    state.effect_target.remove(id)?;
+    state.item_owner.remove(id)?;
+    state.entity_location.remove(id)?;
+
+    for association in state.links.values_mut() {
+        association.remove(id)?;
+    }
+
+    Ok(state)
+}
+
+/// The Public Ids whose values were added, removed, or changed in a component
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ComponentDiff {
+    pub added: Vec<PubId>,
+    pub removed: Vec<PubId>,
+    pub changed: Vec<PubId>,
+}
+
+impl ComponentDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The changes between two states, per component
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct StateDiff {
+    pub name: ComponentDiff,
+    pub description: ComponentDiff,
+    pub turn_state: ComponentDiff,
+    pub entity_type: ComponentDiff,
+    pub turn_order: ComponentDiff,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty()
+            && self.description.is_empty()
+            && self.turn_state.is_empty()
+            && self.entity_type.is_empty()
+            && self.turn_order.is_empty()
+    }
+}
+
+/// QUERY > Compare two states, listing the Public Ids that changed per component
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let old = State::default()
+///     .apply( Scenario::Add(100) )
+///     .apply( Character::Add(200, "ACharacter".into()) )
+///     .apply( Character::Add(300, "BCharacter".into()) )
+///     .apply( Scenario::CaptureEntity(100, 300) )
+///     .unwrap();
+///
+/// let new = old.clone()
+///     .apply( Character::Rename(200, "Renamed".into()) )
+///     .apply( SeqPlay::GrantTurn(100, 300) )
+///     .unwrap();
+///
+/// let diff = state::diff(&old, &new);
+///
+/// assert_eq!(diff.name.changed, vec![200]);
+/// assert_eq!(diff.turn_order.added, vec![100]);
+/// assert_eq!(diff.turn_state.added, vec![300]);
+/// assert!(diff.name.added.is_empty());
+/// assert!(diff.description.is_empty());
+/// assert!(diff.entity_type.is_empty());
+///
+/// assert!(state::diff(&new, &new).is_empty());
+///
+/// // Appending a new entity's turn to an existing sequence changes it
+/// let appended = new.clone()
+///     .apply( Character::Add(400, "CCharacter".into()) )
+///     .apply( SeqPlay::AddTurn(100, 400) )
+///     .unwrap();
+///
+/// let diff = state::diff(&new, &appended);
+///
+/// assert_eq!(diff.turn_order.changed, vec![100]);
+/// assert_eq!(diff.turn_state.added, vec![400]);
+/// ```
+pub fn diff(old: &State, new: &State) -> StateDiff {
+    StateDiff {
+        name: component_diff(old, &old.name, new, &new.name, |_, name| name.clone()),
+        description: component_diff(old, &old.description, new, &new.description, |_, description| description.clone()),
+        // Turn states are keyed by scenario, so compare each entity's resolved status
+        turn_state: values_diff(turn_states(old), turn_states(new)),
+        entity_type: component_diff(old, &old.entity_type, new, &new.entity_type, |_, entity_type| entity_type.clone()),
+        // Turn orders hold internal Ids, so compare them as Public Ids of their own state
+        turn_order: component_diff(old, &old.turn_order, new, &new.turn_order, |state, sequence| {
+            entity::qry::pub_ids(state, sequence.clone())
+        }),
+    }
+}
+
+fn component_diff<CV: Clone + Eq, T: PartialEq>(
+    old: &State,
+    old_component: &Component<CV>,
+    new: &State,
+    new_component: &Component<CV>,
+    comparable: impl Fn(&State, &CV) -> T,
+) -> ComponentDiff {
+    let comparables = |state: &State, component: &Component<CV>| -> HashMap<PubId, T> {
+        by_pub_id(state, component)
+            .into_iter()
+            .map(|(pub_id, value)| (pub_id, comparable(state, value)))
+            .collect()
+    };
+    values_diff(comparables(old, old_component), comparables(new, new_component))
+}
+
+fn values_diff<V: PartialEq>(
+    old_values: HashMap<PubId, V>,
+    new_values: HashMap<PubId, V>,
+) -> ComponentDiff {
+    let mut diff = ComponentDiff::default();
+
+    for (pub_id, new_value) in &new_values {
+        match old_values.get(pub_id) {
+            None => diff.added.push(*pub_id),
+            Some(old_value) if old_value != new_value => diff.changed.push(*pub_id),
+            _ => {}
+        }
+    }
+
+    diff.removed = old_values
+        .keys()
+        .filter(|pub_id| !new_values.contains_key(pub_id))
+        .copied()
+        .collect();
+
+    diff.added.sort();
+    diff.removed.sort();
+    diff.changed.sort();
+    diff
+}
+
+fn turn_states(state: &State) -> HashMap<PubId, TurnStatus> {
+    state.turn_state
+        .iter()
+        .filter_map(|((_, id), _)| entity::qry::pub_id(state, id))
+        .map(|pub_id| (pub_id, turn_state::qry::get(state, pub_id)))
+        .filter(|(_, status)| *status != TurnStatus::None)
+        .collect()
+}
+
+fn by_pub_id<'a, CV: Clone + Eq>(state: &State, component: &'a Component<CV>) -> HashMap<PubId, &'a CV> {
+    component
+        .iter()
+        .filter_map(|(id, value)| Some((entity::qry::pub_id(state, id)?, value)))
+        .collect()
+}
+
+/// COMMAND > Merge the entities of another state into a base state
+///
+/// Entities from `other` keep their Public Ids but are registered under fresh
+/// internal Ids in `base`. Components, hierarchies, links, and turn orders are
+/// carried over with the new Ids. Public Ids that exist in both states are an error.
+/// Entries in `other` that reference Ids missing from its registry are skipped,
+/// as `state::repair` would prune them.
+///
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let base = State::default()
+///     .apply( Scenario::Add(100) )
+///     .apply( Character::Add(200, "ACharacter".into()) )
+///     .apply( SeqPlay::AddTurn(100, 200) )
+///     .unwrap();
+///
+/// let other = State::default()
+///     .apply( Scenario::Add(300) )
+///     .apply( Character::Add(400, "BCharacter".into()) )
+///     .apply( Player::Add(500, "APlayer".into()) )
+///     .apply( Character::AssignPlayer(400, 500) )
+///     .apply( SeqPlay::AddTurn(300, 400) )
+///     .unwrap();
+///
+/// let merged = state::merge(base.clone(), other.clone()).unwrap();
+///
+/// assert!(character::qry::exists(&merged, 200));
+/// assert!(character::qry::exists(&merged, 400));
+/// assert_eq!(character::qry::name(&merged, 400), "BCharacter".to_string());
+/// assert_eq!(character::qry::player(&merged, 400), Some(500));
+/// assert_eq!(turn_order::qry::sequence(&merged, 100), vec![200]);
+/// assert_eq!(turn_order::qry::sequence(&merged, 300), vec![400]);
+/// assert_eq!(scenario::qry::find_entity(&merged, 400), Some(300));
+///
+/// // Public Ids can't collide
+/// assert!(state::merge(merged, other).is_err());
+///
+/// // Dangling entries are skipped rather than merged
+/// let dangling = State::default()
+///     .apply( Character::Add(600, "CCharacter".into()) )
+///     .apply( |state| description::cmd::set(state, 999, "Nobody") )
+///     .unwrap();
+///
+/// let merged = state::merge(State::default(), dangling).unwrap();
+///
+/// assert!(character::qry::exists(&merged, 600));
+/// assert_eq!(state::validate(&merged), Vec::<String>::new());
+/// ```
+pub fn merge(mut base: State, other: State) -> CmdResult<State> {
+    let pub_ids = other.registry.pub_ids();
+
+    if let Some(pub_id) = pub_ids.iter().find(|pub_id| base.registry.has_pub_id(pub_id)) {
+        return Err(format!("Can not merge states that both have an entity with Public Id {}", pub_id));
+    }
+
+    let mut ids: HashMap<Id, Id> = HashMap::new();
+    for pub_id in pub_ids {
+        base = registry::register(base, pub_id)?;
+        ids.insert(other.registry.id(&pub_id), base.registry.id(&pub_id));
+    }
+
+    let id = |id: &Id| ids.get(id).copied();
+    let turn_key = |(scenario_id, id): &TurnKey| {
+        // Scenario Id 0 marks a turn state that isn't scoped to a scenario
+        let scenario_id = match scenario_id {
+            0 => 0,
+            scenario_id => *ids.get(scenario_id)?,
+        };
+        Some((scenario_id, *ids.get(id)?))
+    };
+
+    merge_component(&other.entity_type, id, |id, value| base.entity_type.insert(id, value))?;
+    merge_component(&other.name, id, |id, value| base.name.insert(id, value))?;
+    merge_component(&other.description, id, |id, value| base.description.insert(id, value))?;
+    merge_component(&other.turn_state, turn_key, |key, value| base.turn_state.insert(key, value))?;
+    merge_component(&other.turn_count, turn_key, |key, value| base.turn_count.insert(key, value))?;
+    merge_component(&other.round_count, id, |id, value| base.round_count.insert(id, value))?;
+    merge_component(&other.initiative, id, |id, value| base.initiative.insert(id, value))?;
+    merge_component(&other.tags, id, |id, value| base.tags.insert(id, value))?;
+    let stat_key = |(id, stat_name): &StatKey| Some((*ids.get(id)?, stat_name.clone()));
+    merge_component(&other.stats, stat_key, |key, value| base.stats.insert(key, value))?;
+    merge_component(&other.stat_stash, id, |id, value| base.stat_stash.insert(id, value))?;
+    merge_component(&other.effect_duration, id, |id, value| base.effect_duration.insert(id, value))?;
+
+    for (scenario_id, sequence) in other.turn_order.iter() {
+        if let Some(scenario_id) = ids.get(&scenario_id) {
+            let sequence = sequence.iter().filter_map(|id| ids.get(id).copied()).collect();
+            base.turn_order.insert(*scenario_id, sequence)?;
+        }
+    }
+
+    merge_hierarchy(&mut base.character_player, &other.character_player, &ids)?;
+    merge_hierarchy(&mut base.scenario_entity, &other.scenario_entity, &ids)?;
+    merge_hierarchy(&mut base.effect_source, &other.effect_source, &ids)?;
+    merge_hierarchy(&mut base.effect_target, &other.effect_target, &ids)?;
+    merge_hierarchy(&mut base.item_owner, &other.item_owner, &ids)?;
+    merge_hierarchy(&mut base.entity_location, &other.entity_location, &ids)?;
+
+    for (relation, association) in other.links.iter() {
+        let base_association = base.links.entry(relation.clone()).or_default();
+        for (id, assoc_id) in association.id_dict.iter() {
+            if let (Some(id), Some(assoc_id)) = (ids.get(id), ids.get(assoc_id)) {
+                base_association.assign(*id, *assoc_id)?;
+            }
+        }
+    }
+
+    Ok(base)
+}
+
+/// Insert each of `other`'s values whose key maps into the base state.
+/// Keys that don't map reference dangling Ids and are skipped.
+fn merge_component<CV: ComponentValue, K: ComponentKey>(
+    other: &Component<CV, K>,
+    key: impl Fn(&K) -> Option<K>,
+    mut insert: impl FnMut(K, CV) -> CmdResult<()>,
+) -> CmdResult<()> {
+    for (other_key, value) in other.iter() {
+        if let Some(base_key) = key(&other_key) {
+            insert(base_key, value.clone())?;
+        }
+    }
+    Ok(())
+}
+
+fn merge_hierarchy(base: &mut Hierarchy, other: &Hierarchy, ids: &HashMap<Id, Id>) -> CmdResult<()> {
+    let mut parents: Vec<&Id> = ids.keys().collect();
+    parents.sort();
+    for parent in parents {
+        for child in other.children(*parent) {
+            if let Some(child) = ids.get(&child) {
+                base.set_parent(*child, ids[parent])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// QUERY > Check the invariants that tie a state's parts together
+///
+/// Every component key, hierarchy relationship, link, and turn must reference
+/// a live registry Id. Turns must belong to supported entity types captured by their
+/// scenario, and scenarios can't be nested within themselves. A description of
+/// each violation is returned, so a healthy state has none.
+///
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let state = State::default()
+///     .apply( Scenario::Add(100) )
+///     .apply( Character::Add(200, "ACharacter".into()) )
+///     .apply( Player::Add(300, "APlayer".into()) )
+///     .apply( Character::AssignPlayer(200, 300) )
+///     .apply( SeqPlay::AddTurn(100, 200) )
+///     .apply( SeqPlay::Enable(100) )
+///     .apply( Link::Assign("rival", 200, 300) )
+///     .unwrap();
+///
+/// assert_eq!(state::validate(&state), Vec::<String>::new());
+///
+/// // Inject a name for an Id that was never registered
+/// let mut state = state;
+/// state.name.insert(99, "Ghost".into()).unwrap();
+///
+/// assert_eq!(state::validate(&state), vec!["name references unknown Id 99".to_string()]);
+///
+/// // Links are checked on both sides
+/// let mut state = state;
+/// state.name.delete(99).unwrap();
+/// state.links.entry("ally".into()).or_default().assign(97, 98).unwrap();
+///
+/// assert_eq!(state::validate(&state), vec![
+///     "links[ally] references unknown Id 97".to_string(),
+///     "links[ally] references unknown Id 98".to_string(),
+/// ]);
+/// ```
+pub fn validate(state: &State) -> Vec<String> {
+    let mut violations: Vec<String> = Vec::new();
+
+    for (part, ids) in references(state) {
+        for id in dead_ids(state, ids) {
+            violations.push(format!("{} references unknown Id {}", part, id));
+        }
+    }
+
+    let mut turn_pub_ids: Vec<PubId> = state.turn_order
+        .iter()
+        .flat_map(|(_, sequence)| sequence.clone())
+        .filter_map(|id| entity::qry::pub_id(state, id))
+        .collect();
+    turn_pub_ids.sort();
+    turn_pub_ids.dedup();
+
+    for pub_id in turn_pub_ids {
+        if !turn_order::qry::is_supported_turn_order_type(state, pub_id) {
+            violations.push(format!("Entity {} has a turn but can't take turns", pub_id));
         }
     }
+
+    for (scenario_pub_id, pub_id) in qry::turn_order_orphans(state) {
+        violations.push(format!("Entity {} has a turn in scenario {} but isn't captured by it", pub_id, scenario_pub_id));
+    }
+
+    let nested_pub_ids: Vec<PubId> = state.scenario_entity
+        .pairs()
+        .into_iter()
+        .filter(|(child, _)| is_nested_within_itself(&state.scenario_entity, *child))
+        .filter_map(|(child, _)| entity::qry::pub_id(state, child))
+        .collect();
+
+    for pub_id in nested_pub_ids {
+        violations.push(format!("Scenario {} is nested within itself", pub_id));
+    }
+
+    violations
+}
+
+/// COMMAND > Remove the component, hierarchy, link, and turn order entries that
+/// reference dead registry Ids
+///
+/// Recovers partially corrupted save files. Each pruned reference is
+/// reported; see `state::validate` for the invariants that are checked.
+///
+/// ```
+/// use yourupnext::prelude::*;
+///
+/// let mut state = State::default()
+///     .apply( Character::Add(100, "ACharacter".into()) )
+///     .unwrap();
+///
+/// // Inject a name for an Id that was never registered
+/// state.name.insert(99, "Ghost".into()).unwrap();
+///
+/// let (state, pruned) = state::repair(state);
+///
+/// assert_eq!(pruned, vec!["Pruned name entries for unknown Id 99".to_string()]);
+/// assert!( !state.name.is_set(99) );
+/// assert_eq!(character::qry::name(&state, 100), "ACharacter".to_string());
+/// assert_eq!(state::validate(&state), Vec::<String>::new());
+///
+/// // Dangling links are pruned too
+/// let mut state = state;
+/// state.links.entry("ally".into()).or_default().assign(1, 98).unwrap();
+///
+/// let (state, pruned) = state::repair(state);
+///
+/// assert_eq!(pruned, vec!["Pruned links[ally] entries for unknown Id 98".to_string()]);
+/// assert!( !state.links["ally"].has_assoc(&1) );
+///
+/// // A healthy state is left alone
+/// let (repaired, pruned) = state::repair(state.clone());
+/// assert!(pruned.is_empty());
+/// assert_eq!(repaired, state);
+/// ```
+pub fn repair(mut state: State) -> (State, Vec<String>) {
+    let mut pruned: Vec<String> = Vec::new();
+    let mut dead: Vec<Id> = Vec::new();
+
+    for (part, ids) in references(&state) {
+        for id in dead_ids(&state, ids) {
+            pruned.push(format!("Pruned {} entries for unknown Id {}", part, id));
+            dead.push(id);
+        }
+    }
+
+    dead.sort();
+    dead.dedup();
+
+    for id in dead {
+        match purge_entity(state.clone(), id) {
+            Ok(purged) => state = purged,
+            Err(err) => pruned.push(format!("Unable to prune unknown Id {}: {}", id, err)),
+        }
+    }
+
+    (state, pruned)
+}
+
+/// The Ids referenced by each component, hierarchy, and link relation, named by field
+fn references(state: &State) -> Vec<(String, Vec<Id>)> {
+    let mut references: Vec<(String, Vec<Id>)> = vec![
+        ("entity_type".to_string(), state.entity_type.ids()),
+        ("name".to_string(), state.name.ids()),
+        ("description".to_string(), state.description.ids()),
+        ("turn_state".to_string(), turn_key_ids(&state.turn_state)),
+        ("turn_count".to_string(), turn_key_ids(&state.turn_count)),
+        ("turn_order".to_string(), turn_order_ids(&state.turn_order)),
+        ("round_count".to_string(), state.round_count.ids()),
+        ("initiative".to_string(), state.initiative.ids()),
+        ("tags".to_string(), state.tags.ids()),
+        ("stats".to_string(), state.stats.ids().into_iter().map(|(id, _)| id).collect()),
+        ("stat_stash".to_string(), state.stat_stash.ids()),
+        ("effect_duration".to_string(), state.effect_duration.ids()),
+        ("character_player".to_string(), hierarchy_ids(&state.character_player)),
+        ("scenario_entity".to_string(), hierarchy_ids(&state.scenario_entity)),
+        ("effect_source".to_string(), hierarchy_ids(&state.effect_source)),
+        ("effect_target".to_string(), hierarchy_ids(&state.effect_target)),
+        ("item_owner".to_string(), hierarchy_ids(&state.item_owner)),
+        ("entity_location".to_string(), hierarchy_ids(&state.entity_location)),
+    ];
+
+    let mut relations: Vec<&String> = state.links.keys().collect();
+    relations.sort();
+    for relation in relations {
+        let association = &state.links[relation];
+        let ids = association.id_dict
+            .iter()
+            .flat_map(|(id, assoc_id)| [*id, *assoc_id])
+            .collect();
+        references.push((format!("links[{}]", relation), ids));
+    }
+
+    references
+}
+
+fn dead_ids(state: &State, mut ids: Vec<Id>) -> Vec<Id> {
+    ids.retain(|id| !state.registry.has_id(id));
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+fn turn_key_ids<CV: Clone + Eq>(component: &Component<CV, TurnKey>) -> Vec<Id> {
+    // Scenario Id 0 marks a turn state that isn't scoped to a scenario
+    component
+        .ids()
+        .into_iter()
+        .flat_map(|(scenario_id, id)| [scenario_id, id])
+        .filter(|id| *id != 0)
+        .collect()
+}
+
+fn turn_order_ids(component: &Component<TurnOrder>) -> Vec<Id> {
+    component
+        .iter()
+        .flat_map(|(scenario_id, sequence)| std::iter::once(scenario_id).chain(sequence.iter().copied()))
+        .collect()
+}
+
+fn hierarchy_ids(hierarchy: &Hierarchy) -> Vec<Id> {
+    hierarchy
+        .pairs()
+        .into_iter()
+        .flat_map(|(child, parent)| [child, parent])
+        .collect()
+}
+
+/// Walks up from a child without `Hierarchy::ancestors`, which never ends on a cycle
+fn is_nested_within_itself(hierarchy: &Hierarchy, child: Id) -> bool {
+    let mut visited: Vec<Id> = vec![child];
+    let mut current = child;
+    while let Some(parent) = hierarchy.parent(current) {
+        if parent == child {
+            return true;
+        }
+        if visited.contains(&parent) {
+            return false;
+        }
+        visited.push(parent);
+        current = parent;
+    }
+    false
+}
+
+pub mod cmd {
+    use super::*;
+
+    /// COMMAND > Synchronize every scenario's turn order with its membership
+    ///
+    /// Turns held by entities that aren't members of the scenario are removed.
+    /// When `append_missing` is set, members that can take a turn but don't
+    /// have one are granted a turn at the end of the sequence.
+    ///
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( Character::Add(400, "CCharacter".into()) )
+    ///     .apply( SeqPlay::AddTurn(100, 200) )
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 100, 300) )
+    ///     .apply( Scenario::CaptureEntity(100, 400) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(state::qry::turn_order_orphans(&state), vec![(100, 300)]);
+    ///
+    /// let synced = state.clone()
+    ///     .apply( |state| state::cmd::sync_turn_orders(state, false) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(state::qry::turn_order_orphans(&synced), vec![]);
+    /// assert_eq!(turn_order::qry::sequence(&synced, 100), vec![200]);
+    ///
+    /// let synced = state
+    ///     .apply( |state| state::cmd::sync_turn_orders(state, true) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(state::qry::turn_order_orphans(&synced), vec![]);
+    /// assert_eq!(turn_order::qry::sequence(&synced, 100), vec![200, 400]);
+    /// ```
+    pub fn sync_turn_orders(mut state: State, append_missing: bool) -> CmdResult<State> {
+        for scenario_id in state.turn_order.ids() {
+            let mut turns = state.turn_order.get_or_default(scenario_id);
+            turns.retain(|entity_id| state.scenario_entity.parent(*entity_id) == Some(scenario_id));
+            state.turn_order.update(scenario_id, turns)?;
+        }
+
+        if !append_missing {
+            return Ok(state);
+        }
+
+        let missing_turns: Vec<(PubId, PubId)> = state.registry
+            .pub_ids()
+            .into_iter()
+            .filter(|pub_id| scenario::qry::exists(&state, *pub_id))
+            .flat_map(|scenario_pub_id| {
+                scenario::qry::members(&state, scenario_pub_id)
+                    .into_iter()
+                    .filter(|member| turn_order::qry::is_supported_turn_order_type(&state, *member))
+                    .filter(|member| !turn_order::qry::contains(&state, scenario_pub_id, *member))
+                    .map(move |member| (scenario_pub_id, member))
+                    .collect::<Vec<(PubId, PubId)>>()
+            })
+            .collect();
+
+        state.apply_with(missing_turns, |(scenario_pub_id, member)| {
+            SeqPlay::GrantTurn(scenario_pub_id, member)
+        })
+    }
 }
 
 pub mod qry {
@@ -42,4 +789,43 @@ pub mod qry {
     // ids
     // pub_id
     // pub_ids
+
+    /// QUERY > Get the (scenario, entity) Public Id pairs where an entity has a turn
+    /// in a scenario that it isn't a member of
+    /// ```
+    /// use yourupnext::prelude::*;
+    ///
+    /// let state = State::default()
+    ///     .apply( Scenario::Add(100) )
+    ///     .apply( Character::Add(200, "ACharacter".into()) )
+    ///     .apply( Character::Add(300, "BCharacter".into()) )
+    ///     .apply( SeqPlay::AddTurn(100, 200) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(state::qry::turn_order_orphans(&state), vec![]);
+    ///
+    /// // Adding a turn directly skips capturing the entity in the scenario
+    /// let state = state
+    ///     .apply( |state| turn_order::cmd::add_turn(state, 100, 300) )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(state::qry::turn_order_orphans(&state), vec![(100, 300)]);
+    /// ```
+    pub fn turn_order_orphans(state: &State) -> Vec<(PubId, PubId)> {
+        state.turn_order
+            .ids()
+            .into_iter()
+            .flat_map(|scenario_id| {
+                state.turn_order
+                    .get(scenario_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(move |entity_id| state.scenario_entity.parent(*entity_id) != Some(scenario_id))
+                    .map(move |entity_id| (scenario_id, entity_id))
+            })
+            .filter_map(|(scenario_id, entity_id)| {
+                Some((entity::qry::pub_id(state, scenario_id)?, entity::qry::pub_id(state, entity_id)?))
+            })
+            .collect()
+    }
 }
\ No newline at end of file
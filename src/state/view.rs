@@ -0,0 +1,81 @@
+/// # Session View
+/// A read-only, serializable projection of `State` for UIs. Views only use
+/// Public Ids and names so that frontends never see internal ids.
+use crate::prelude::*;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct SessionView {
+    pub scenarios: Vec<ScenarioView>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ScenarioView {
+    pub pub_id: PubId,
+    pub name: String,
+    pub status: TurnStatus,
+    pub members: Vec<EntityView>,
+    pub turns: Vec<TurnInfo>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct EntityView {
+    pub pub_id: PubId,
+    pub name: String,
+}
+
+/// QUERY > Build a view of every scenario, ordered by Public Id
+/// ```
+/// use yourupnext::prelude::*;
+/// use yourupnext::state::view::{self, EntityView};
+///
+/// let state = State::default()
+///     .apply( Scenario::Add(50) )
+///     .apply( Entity::Name(50, "Ambush".into()) )
+///     .apply( Character::Add(100, "A".into()) )
+///     .apply( Character::Add(200, "B".into()) )
+///     .apply( SeqPlay::AddTurn(50, 100) )
+///     .apply( SeqPlay::AddTurn(50, 200) )
+///     .apply( SeqPlay::Enable(50) )
+///     .apply( SeqPlay::NextTurn(50) )
+///     .unwrap();
+///
+/// let session = view::build(&state);
+///
+/// assert_eq!(session.scenarios.len(), 1);
+///
+/// let scenario = &session.scenarios[0];
+/// assert_eq!(scenario.pub_id, 50);
+/// assert_eq!(scenario.name, "Ambush".to_string());
+/// assert_eq!(scenario.status, TurnStatus::Active);
+/// assert_eq!(scenario.members, vec![
+///     EntityView { pub_id: 100, name: "A".to_string() },
+///     EntityView { pub_id: 200, name: "B".to_string() },
+/// ]);
+/// assert_eq!(scenario.turns, seq_play::qry::summary(&state, 50));
+/// assert_eq!(scenario.turns[0].status, TurnStatus::Active);
+/// assert_eq!(scenario.turns[1].status, TurnStatus::Available);
+///
+/// assert_eq!(view::build(&State::default()), view::SessionView::default());
+/// ```
+pub fn build(state: &State) -> SessionView {
+    let scenarios = entity::qry::all(state)
+        .into_iter()
+        .filter(|pub_id| entity_type::qry::is(state, *pub_id, EntityType::Scenario))
+        .map(|scenario_pub_id| ScenarioView {
+            pub_id: scenario_pub_id,
+            name: name::qry::get(state, scenario_pub_id),
+            status: turn_state::qry::get(state, scenario_pub_id),
+            members: scenario::qry::members(state, scenario_pub_id)
+                .into_iter()
+                .map(|pub_id| EntityView {
+                    pub_id,
+                    name: name::qry::get(state, pub_id),
+                })
+                .collect(),
+            turns: seq_play::qry::summary(state, scenario_pub_id),
+        })
+        .collect();
+
+    SessionView { scenarios }
+}
@@ -24,6 +24,29 @@ impl Association {
         Ok(())
     }
 
+    /// Remove any association an Id takes part in, from either side
+    /// ```
+    /// use yourupnext::prelude::Association;
+    ///
+    /// let mut a = Association::default();
+    /// let _ = a.assign(1, 2);
+    /// let _ = a.assign(3, 4);
+    /// let _ = a.remove(2);
+    ///
+    /// assert!( !a.has_assoc(&1) );
+    /// assert!( !a.is_assoc_id(&2) );
+    /// assert!( a.has_assoc(&3) );
+    /// ```
+    pub fn remove(&mut self, id: Id) -> CmdResult<()> {
+        if let Some(assoc_id) = self.id_dict.remove(&id) {
+            self.assoc_id_dict.remove(&assoc_id);
+        }
+        if let Some(assoc_owner_id) = self.assoc_id_dict.remove(&id) {
+            self.id_dict.remove(&assoc_owner_id);
+        }
+        Ok(())
+    }
+
     pub fn has_assoc(&self, id: &Id) -> bool {
         self.id_dict.contains_key(id)
     }
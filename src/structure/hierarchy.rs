@@ -114,8 +114,8 @@ impl Hierarchy {
     /// let _ = h.set_parent(2, 0);
     ///
     /// assert_eq!( h.children(0), vec![1,2]);
-    /// assert_eq!( h.children(1), vec![]);
-    /// assert_eq!( h.children(3), vec![]);
+    /// assert_eq!( h.children(1), Vec::<usize>::new());
+    /// assert_eq!( h.children(3), Vec::<usize>::new());
     /// ```
     pub fn children(&self, parent: Id) -> Vec<Id> {
         match self.parent_children.get(&parent) {
@@ -202,6 +202,24 @@ impl Hierarchy {
         ancetors
     }
 
+    /// Get every (child, parent) relationship, ordered by child
+    /// ```
+    /// use yourupnext::prelude::Hierarchy;
+    /// let mut h = Hierarchy::new();
+    /// let _ = h.set_parent(2, 1);
+    /// let _ = h.set_parent(1, 0);
+    ///
+    /// assert_eq!( h.pairs(), vec![(1,0),(2,1)]);
+    /// ```
+    pub fn pairs(&self) -> Vec<(Id, Id)> {
+        let mut pairs: Vec<(Id, Id)> = self.child_parent
+            .iter()
+            .map(|(child, parent)| (*child, *parent))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
 
     // ----------------------------------------------------------------------
     // Command
@@ -256,7 +274,7 @@ impl Hierarchy {
     ///
     /// assert_eq!( h.parent_count(), 2 );
     /// assert_eq!( h.child_count(), 2 );
-    /// assert_eq!( h.children(0), vec![] );
+    /// assert_eq!( h.children(0), Vec::<usize>::new() );
     /// assert_eq!( h.lineage(4), vec![2,3,4] );
     /// ```
     pub fn remove_parent(&mut self, child: Id) -> CmdResult<()> {
@@ -303,6 +321,28 @@ impl Hierarchy {
         Ok(())
     }
 
+    /// Remove an Id from a hierarchy entirely, both as a child and as a parent
+    /// ```
+    /// use yourupnext::prelude::Hierarchy;
+    ///
+    /// let mut h = Hierarchy::new();
+    /// let _ = h.set_parent(1, 0);
+    /// let _ = h.set_parent(2, 1);
+    /// let _ = h.remove(1);
+    ///
+    /// assert!( ! h.is_child(1) );
+    /// assert!( ! h.is_parent(1) );
+    /// assert!( ! h.is_child(2) );
+    /// assert_eq!( h.parent_count(), 0 );
+    /// assert_eq!( h.child_count(), 0 );
+    /// ```
+    pub fn remove(&mut self, id: Id) -> CmdResult<()> {
+        if self.is_child(id) {
+            self.remove_parent(id)?;
+        }
+        self.free_children_from(id)
+    }
+
     // maybe create "adopt from parent (source -> destination) to move a branch?
     // or "Transfer Children"
 